@@ -1,47 +1,288 @@
-use anyhow::anyhow;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-use ureq::{self, Error as UreqError};
+use std::thread;
+use std::time::Instant;
 
-use crate::types::{HorizonError, HttpMethod};
+use crate::endpoints::Server;
+use crate::transport::{
+    endpoint, CacheLookup, ErrorClass, HttpRequest, HttpResponse, RequestMetrics, RequestSpan,
+    ResponseCache, RetryPolicy, NO_RETRY_POLICY,
+};
+use crate::types::HttpMethod;
+use crate::Error;
 
 pub fn api_call<T: DeserializeOwned>(
     url: String,
     method: HttpMethod,
     query_params: &HashMap<String, String>,
-    token: &Option<String>,
-) -> Result<T, anyhow::Error> {
-    let mut req = match method {
-        HttpMethod::GET => ureq::get(&url),
-        HttpMethod::POST => ureq::post(&url),
-    };
-    if token.is_some() {
-        req = req.set("Authorization", token.clone().unwrap().as_str());
+    server: &Server,
+) -> Result<T, Error> {
+    let started = Instant::now();
+    let req = request(&url, method, query_params, server);
+    let result = send(req, server, false).and_then(|res| parse_response(&url, res));
+
+    record_metrics(
+        server,
+        method,
+        &url,
+        started,
+        result.as_ref().err().map(Into::into),
+    );
+    result
+}
+
+/// Like [`api_call`], for resources that never change once they exist, e.g. a ledger
+/// loaded by sequence, which the response cache keeps for good.
+pub fn api_call_immutable<T: DeserializeOwned>(url: String, server: &Server) -> Result<T, Error> {
+    let started = Instant::now();
+    let req = request(&url, HttpMethod::GET, &HashMap::new(), server);
+    let result = send(req, server, true).and_then(|res| parse_response(&url, res));
+
+    let error = result.as_ref().err().map(Into::into);
+    record_metrics(server, HttpMethod::GET, &url, started, error);
+    result
+}
+
+fn parse_response<T: DeserializeOwned>(url: &str, res: HttpResponse) -> Result<T, Error> {
+    if !res.is_success() {
+        return Err(Error::from_response(url, res));
     }
 
-    for query_param in query_params.iter() {
-        req = req.query(query_param.0, query_param.1);
+    Ok(serde_json::from_str::<T>(&res.body)?)
+}
+
+fn request(
+    url: &str,
+    method: HttpMethod,
+    query_params: &HashMap<String, String>,
+    server: &Server,
+) -> HttpRequest {
+    HttpRequest::new(method, url)
+        .with_query_params(query_params)
+        .with_headers(server.options.request_headers())
+}
+
+fn retry_policy(server: &Server) -> &RetryPolicy {
+    server
+        .options
+        .retry_policy
+        .as_ref()
+        .unwrap_or(&NO_RETRY_POLICY)
+}
+
+// Hands the outcome of a call to the metrics sink of the server, if any
+fn record_metrics(
+    server: &Server,
+    method: HttpMethod,
+    url: &str,
+    started: Instant,
+    error: Option<ErrorClass>,
+) {
+    if let Some(metrics) = &server.options.metrics {
+        metrics.record(&RequestMetrics {
+            method,
+            endpoint: endpoint(url),
+            latency: started.elapsed(),
+            error,
+        });
     }
+}
 
-    match req.call() {
-        Ok(res) => {
-            let res_str = res.into_string()?;
+// Responses of send_request are handed back whatever their status, yet count as errors
+fn response_error(result: &Result<HttpResponse, Error>) -> Option<ErrorClass> {
+    match result {
+        Ok(res) if res.is_success() => None,
+        Ok(res) => Some(ErrorClass::from_response(res)),
+        Err(e) => Some(e.into()),
+    }
+}
+
+// Only GET responses are cached
+fn response_cache<'a>(req: &HttpRequest, server: &'a Server) -> Option<&'a ResponseCache> {
+    match req.method {
+        HttpMethod::GET => server.options.cache.as_deref(),
+        _ => None,
+    }
+}
+
+/// Sends the request and hands back the response whatever its status, for the
+/// endpoints whose non 2xx bodies are not problem documents. Transient failures are
+/// retried according to the server's [`RetryPolicy`].
+pub fn send_request(
+    url: &str,
+    method: HttpMethod,
+    query_params: &HashMap<String, String>,
+    server: &Server,
+) -> Result<HttpResponse, Error> {
+    let started = Instant::now();
+    let result = send(request(url, method, query_params, server), server, false);
+
+    record_metrics(server, method, url, started, response_error(&result));
+    result
+}
+
+fn send(mut req: HttpRequest, server: &Server, immutable: bool) -> Result<HttpResponse, Error> {
+    let span = RequestSpan::new(&req);
+    let cache = response_cache(&req, server);
+    if let Some(cache) = cache {
+        match cache.lookup(&req) {
+            CacheLookup::Fresh(res) => {
+                span.cache_hit();
+                return Ok(res);
+            }
+            CacheLookup::Stale(etag) => req = req.with_header("If-None-Match", &etag),
+            CacheLookup::Miss => {}
+        }
+    }
+
+    let res = send_with_retries(&req, server, &span)?;
+
+    Ok(match cache {
+        Some(cache) => cache.store(&req, res, immutable),
+        None => res,
+    })
+}
+
+fn send_with_retries(
+    req: &HttpRequest,
+    server: &Server,
+    span: &RequestSpan,
+) -> Result<HttpResponse, Error> {
+    let mut attempt = 1;
+    loop {
+        if let Some(rate_limiter) = &server.options.rate_limiter {
+            thread::sleep(rate_limiter.reserve());
+        }
 
-            Ok(serde_json::from_str::<T>(&res_str)?)
+        let res = server.transport.send(req);
+        if let Ok(res) = &res {
+            server.observe_rate_limit(res);
         }
-        Err(e) => match e {
-            UreqError::Status(code, res) => {
-                if code >= 500 {
-                    return Err(UreqError::Status(code, res).into());
-                }
 
-                let res_str = res.into_string()?;
-                Err(match serde_json::from_str::<HorizonError>(&res_str) {
-                    Ok(parsed) => parsed.into(),
-                    Err(_) => anyhow!("Cannot parse error: {}", res_str),
-                })
+        match retry_policy(server).retry_delay(req.method, attempt, &res) {
+            Some(delay) => {
+                span.retry(attempt, delay, &res);
+                thread::sleep(delay);
             }
-            other => Err(other.into()),
-        },
+            None => {
+                span.finish(attempt, &res);
+                return res;
+            }
+        }
+        attempt += 1;
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn api_call_async<T: DeserializeOwned>(
+    url: String,
+    method: HttpMethod,
+    query_params: &HashMap<String, String>,
+    server: &Server,
+) -> Result<T, Error> {
+    let started = Instant::now();
+    let req = request(&url, method, query_params, server);
+    let result = send_async(req, server, false)
+        .await
+        .and_then(|res| parse_response(&url, res));
+
+    record_metrics(
+        server,
+        method,
+        &url,
+        started,
+        result.as_ref().err().map(Into::into),
+    );
+    result
+}
+
+#[cfg(feature = "async")]
+pub async fn api_call_immutable_async<T: DeserializeOwned>(
+    url: String,
+    server: &Server,
+) -> Result<T, Error> {
+    let started = Instant::now();
+    let req = request(&url, HttpMethod::GET, &HashMap::new(), server);
+    let result = send_async(req, server, true)
+        .await
+        .and_then(|res| parse_response(&url, res));
+
+    let error = result.as_ref().err().map(Into::into);
+    record_metrics(server, HttpMethod::GET, &url, started, error);
+    result
+}
+
+#[cfg(feature = "async")]
+pub async fn send_request_async(
+    url: &str,
+    method: HttpMethod,
+    query_params: &HashMap<String, String>,
+    server: &Server,
+) -> Result<HttpResponse, Error> {
+    let started = Instant::now();
+    let result = send_async(request(url, method, query_params, server), server, false).await;
+
+    record_metrics(server, method, url, started, response_error(&result));
+    result
+}
+
+#[cfg(feature = "async")]
+async fn send_async(
+    mut req: HttpRequest,
+    server: &Server,
+    immutable: bool,
+) -> Result<HttpResponse, Error> {
+    let span = RequestSpan::new(&req);
+    let cache = response_cache(&req, server);
+    if let Some(cache) = cache {
+        match cache.lookup(&req) {
+            CacheLookup::Fresh(res) => {
+                span.cache_hit();
+                return Ok(res);
+            }
+            CacheLookup::Stale(etag) => req = req.with_header("If-None-Match", &etag),
+            CacheLookup::Miss => {}
+        }
+    }
+
+    let res = send_with_retries_async(&req, server, &span).await?;
+
+    Ok(match cache {
+        Some(cache) => cache.store(&req, res, immutable),
+        None => res,
+    })
+}
+
+#[cfg(feature = "async")]
+async fn send_with_retries_async(
+    req: &HttpRequest,
+    server: &Server,
+    span: &RequestSpan,
+) -> Result<HttpResponse, Error> {
+    let mut attempt = 1;
+    loop {
+        if let Some(rate_limiter) = &server.options.rate_limiter {
+            tokio::time::sleep(rate_limiter.reserve()).await;
+        }
+
+        let res = match server.async_transport() {
+            Ok(transport) => transport.send(req).await,
+            Err(e) => Err(e),
+        };
+        if let Ok(res) = &res {
+            server.observe_rate_limit(res);
+        }
+
+        match retry_policy(server).retry_delay(req.method, attempt, &res) {
+            Some(delay) => {
+                span.retry(attempt, delay, &res);
+                tokio::time::sleep(delay).await;
+            }
+            None => {
+                span.finish(attempt, &res);
+                return res;
+            }
+        }
+        attempt += 1;
     }
 }
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, Pages, Records, Server};
 use crate::types::{Account, Asset};
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct AccountCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> AccountCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -50,22 +51,45 @@ impl<'a> AccountCallBuilder<'a> {
 
         self
     }
-}
 
-impl<'a> CallBuilder<Account> for AccountCallBuilder<'a> {
-    fn call(&self) -> Result<Record<Account>, anyhow::Error> {
-        let url = format!(
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Account>, Error> {
+        api_call_async::<Record<Account>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Account> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Account> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
             "{}{}{}",
-            &self.server_url,
+            &self.server.server_url,
             self.endpoint.as_str(),
             "/accounts",
-        );
+        )
+    }
+}
+
+impl<'a> CallBuilder<Account> for AccountCallBuilder<'a> {
+    fn call(&self) -> Result<Record<Account>, Error> {
+        let url = self.url();
 
         api_call::<Record<Account>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 
@@ -99,12 +123,37 @@ impl<'a> CallBuilder<Account> for AccountCallBuilder<'a> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
+    use crate::types::account_json;
 
     #[test]
     fn limit_account_call_builder() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let page = serde_json::json!({
+            "_links": {
+                "self": {"href": "https://horizon.stellar.org/accounts?cursor=&limit=3&liquidity_pool=a468d41d8e9b8f3c7209651608b74b7db7ac9952dcae0cdf24871d1d9c7b0088&order=asc"}
+            },
+            "_embedded": {"records": [
+                account_json("https://horizon.stellar.org", "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM", 1),
+                account_json("https://horizon.stellar.org", "GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN", 2),
+                account_json("https://horizon.stellar.org", "GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV", 3),
+            ]}
+        });
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/accounts",
+            &[
+                (
+                    "liquidity_pool",
+                    "a468d41d8e9b8f3c7209651608b74b7db7ac9952dcae0cdf24871d1d9c7b0088",
+                ),
+                ("limit", "3"),
+            ],
+            HttpResponse::new(200, &page.to_string()),
+        );
+        let s = mock_server(&mock);
 
         let mut acb = AccountCallBuilder::new(&s);
 
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, Pages, Records, Server};
 use crate::types::AssetHorizon;
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct AssetCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> AssetCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         AssetCallBuilder {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -36,6 +37,34 @@ impl<'a> AssetCallBuilder<'a> {
 
         self
     }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<AssetHorizon>, Error> {
+        api_call_async::<Record<AssetHorizon>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, AssetHorizon> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, AssetHorizon> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/assets",
+        )
+    }
 }
 
 impl<'a> CallBuilder<AssetHorizon> for AssetCallBuilder<'a> {
@@ -66,31 +95,67 @@ impl<'a> CallBuilder<AssetHorizon> for AssetCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<AssetHorizon>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/assets",
-        );
+    fn call(&self) -> Result<Record<AssetHorizon>, Error> {
+        let url = self.url();
 
         api_call::<Record<AssetHorizon>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn assets_horizon_test() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/assets",
+            &[
+                (
+                    "issuer",
+                    "GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN",
+                ),
+                ("limit", "3"),
+            ],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_links": {
+                        "self": {"href": "https://horizon.stellar.org/assets?asset_issuer=GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN&cursor=&limit=3&order=asc"},
+                        "next": {"href": "https://horizon.stellar.org/assets?asset_issuer=GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN&cursor=NGNT_GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN_credit_alphanum4&limit=3&order=asc"},
+                        "prev": {"href": "https://horizon.stellar.org/assets?asset_issuer=GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN&cursor=NGNT_GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN_credit_alphanum4&limit=3&order=desc"}
+                    },
+                    "_embedded": {"records": [
+                        {
+                            "_links": {"toml": {"href": "https://cowrie.exchange/.well-known/stellar.toml"}},
+                            "asset_type": "credit_alphanum4",
+                            "asset_code": "NGNT",
+                            "asset_issuer": "GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN",
+                            "paging_token": "NGNT_GA5BUT4SND34VRUJGFEVLG6LMEYOU5HSSYZLX673I2IJVRLLPATMH4RN_credit_alphanum4",
+                            "num_claimable_balances": 0,
+                            "num_liquidity_pools": 2,
+                            "num_contracts": 0,
+                            "accounts": {"authorized": 1520, "authorized_to_maintain_liabilities": 0, "unauthorized": 0},
+                            "claimable_balances_amount": "0.0000000",
+                            "liquidity_pools_amount": "1520.0000000",
+                            "contracts_amount": "0.0000000",
+                            "balances": {"authorized": "243500.6162744", "authorized_to_maintain_liabilities": "0.0000000", "unauthorized": "0.0000000"},
+                            "flags": {"auth_required": false, "auth_revocable": false, "auth_immutable": false, "auth_clawback_enabled": false}
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let mut acb = AssetCallBuilder::new(&s);
 
@@ -100,6 +165,7 @@ mod tests {
             .call()
             .unwrap();
 
-        assert_eq!(asset_records._embedded.records.len(), 3);
+        assert_eq!(asset_records._embedded.records.len(), 1);
+        assert_eq!("NGNT", asset_records._embedded.records[0].asset_code);
     }
 }
@@ -0,0 +1,375 @@
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+use std::time::{Duration, Instant};
+
+use crate::api_call::{api_call_async, api_call_immutable_async, send_request_async};
+use crate::endpoints::server::{
+    check_account_memo_required, memo_required_destinations, submit_transaction_async_response,
+    TRANSACTION_POLL_INTERVAL,
+};
+use crate::endpoints::{
+    AccountCallBuilder, AssetCallBuilder, ClaimableBalanceCallbuilder, EffectCallBuilder,
+    LedgerCallBuilder, LiquidityPoolCallBuilder, OfferCallBuilder, OperationCallBuilder,
+    OrderBookCallBuilder, PaymentCallBuilder, Server, ServerOptions, StrictReceiveCallBuilder,
+    StrictSendCallBuilder, TradeAggregationCallBuilder, TradeCallBuilder, TransactionCallBuilder,
+};
+use crate::types::{
+    Account, AccountData, Asset, ClaimableBalance, FeeStats, HttpMethod, Ledger, LiquidityPool,
+    Offer, Operation, StrictPathSource, SubmitTransactionAsyncResponse, SubmitTransactionResponse,
+    Transaction,
+};
+
+use crate::utils::{FeeEstimate, FeeEstimator};
+use crate::Error;
+#[cfg(feature = "nacl")]
+use crate::Keypair;
+#[cfg(feature = "nacl")]
+use stellar_base::Network;
+use stellar_base::{
+    transaction::{FeeBumpTransaction, Transaction as TransactionSBase, TransactionEnvelope},
+    xdr::XDRSerialize,
+};
+
+/// Non-blocking counterpart of [`Server`], enabled by the `async` feature.
+///
+/// Collection endpoints use the same call builders as [`Server`]; await their
+/// `call_async` method instead of calling `call`.
+#[derive(Debug, Clone)]
+pub struct AsyncServer {
+    server: Server,
+}
+
+impl AsyncServer {
+    pub fn new(server_url: String, opts: Option<ServerOptions>) -> Result<Self, Error> {
+        Ok(AsyncServer {
+            server: Server::new(server_url, opts)?,
+        })
+    }
+
+    pub fn server(&self) -> &Server {
+        &self.server
+    }
+
+    pub fn set_auth_token(&mut self, token: String) {
+        self.server.set_auth_token(token);
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: String) -> Result<T, Error> {
+        api_call_async::<T>(url, HttpMethod::GET, &HashMap::new(), &self.server).await
+    }
+
+    pub async fn load_account(&self, account_id: &str) -> Result<Account, Error> {
+        self.get(self.server.url(&["accounts", account_id])).await
+    }
+
+    pub async fn load_account_data(
+        &self,
+        account_id: &str,
+        key: &str,
+    ) -> Result<AccountData, Error> {
+        self.get(self.server.url(&["accounts", account_id, "data", key]))
+            .await
+    }
+
+    pub fn accounts(&self) -> AccountCallBuilder<'_> {
+        self.server.accounts()
+    }
+
+    pub async fn load_transaction(&self, hash: &str) -> Result<Transaction, Error> {
+        api_call_immutable_async(self.server.url(&["transactions", hash]), &self.server).await
+    }
+
+    pub fn transactions(&self) -> TransactionCallBuilder<'_> {
+        self.server.transactions()
+    }
+
+    pub async fn load_ledger(&self, sequence: u64) -> Result<Ledger, Error> {
+        api_call_immutable_async(
+            self.server.url(&["ledgers", &sequence.to_string()]),
+            &self.server,
+        )
+        .await
+    }
+
+    pub fn ledgers(&self) -> LedgerCallBuilder<'_> {
+        self.server.ledgers()
+    }
+
+    pub async fn load_offer(&self, offer_id: &str) -> Result<Offer, Error> {
+        self.get(self.server.url(&["offers", offer_id])).await
+    }
+
+    pub fn offers(&self) -> OfferCallBuilder<'_> {
+        self.server.offers()
+    }
+
+    pub async fn load_operation(&self, operation_id: &str) -> Result<Operation, Error> {
+        api_call_immutable_async(self.server.url(&["operations", operation_id]), &self.server).await
+    }
+
+    pub fn operations(&self) -> OperationCallBuilder<'_> {
+        self.server.operations()
+    }
+
+    pub async fn load_liquidity_pool(
+        &self,
+        liquidity_pool_id: &str,
+    ) -> Result<LiquidityPool, Error> {
+        self.get(self.server.url(&["liquidity_pools", liquidity_pool_id]))
+            .await
+    }
+
+    pub fn liquidity_pools(&self) -> LiquidityPoolCallBuilder<'_> {
+        self.server.liquidity_pools()
+    }
+
+    pub async fn load_claimable_balance(
+        &self,
+        claimable_balance_id: &str,
+    ) -> Result<ClaimableBalance, Error> {
+        self.get(
+            self.server
+                .url(&["claimable_balances", claimable_balance_id]),
+        )
+        .await
+    }
+
+    pub fn claimable_balances(&self) -> ClaimableBalanceCallbuilder<'_> {
+        self.server.claimable_balances()
+    }
+
+    pub fn trade_aggregations<'a>(
+        &'a self,
+        base: &'a Asset,
+        counter: &'a Asset,
+        resolution: &'a str,
+    ) -> TradeAggregationCallBuilder<'a> {
+        self.server.trade_aggregations(base, counter, resolution)
+    }
+
+    pub fn order_books(&self, selling: Asset, buying: Asset) -> OrderBookCallBuilder<'_> {
+        self.server.order_books(selling, buying)
+    }
+
+    pub fn strict_receive_paths<'a>(
+        &'a self,
+        source: &StrictPathSource,
+        destination_asset: Asset,
+        destination_amount: String,
+    ) -> StrictReceiveCallBuilder<'a> {
+        self.server
+            .strict_receive_paths(source, destination_asset, destination_amount)
+    }
+
+    pub fn strict_send_paths<'a>(
+        &'a self,
+        destination: &StrictPathSource,
+        source_asset: &'a Asset,
+        source_amount: &'a str,
+    ) -> StrictSendCallBuilder<'a> {
+        self.server
+            .strict_send_paths(destination, source_asset, source_amount)
+    }
+
+    pub fn trades(&self) -> TradeCallBuilder<'_> {
+        self.server.trades()
+    }
+
+    pub fn payments(&self) -> PaymentCallBuilder<'_> {
+        self.server.payments()
+    }
+
+    pub fn assets(&self) -> AssetCallBuilder<'_> {
+        self.server.assets()
+    }
+
+    pub fn effects(&self) -> EffectCallBuilder<'_> {
+        self.server.effects()
+    }
+
+    pub async fn fee_stats(&self) -> Result<FeeStats, Error> {
+        self.get(self.server.url(&["fee_stats"])).await
+    }
+
+    pub async fn fetch_base_fee(&self) -> Result<String, Error> {
+        let fee_stats = self.fee_stats().await?;
+        Ok(fee_stats.last_ledger_base_fee.to_stroops().to_string())
+    }
+
+    pub async fn estimate_fee(&self, estimator: &FeeEstimator) -> Result<FeeEstimate, Error> {
+        Ok(estimator.estimate(&self.fee_stats().await?))
+    }
+
+    pub async fn submit_transaction(
+        &self,
+        transaction: TransactionSBase,
+    ) -> Result<SubmitTransactionResponse, Error> {
+        self.check_memo_required(&transaction).await?;
+
+        self.submit_envelope(transaction.into_envelope()).await
+    }
+
+    pub async fn submit_transaction_async(
+        &self,
+        transaction: TransactionSBase,
+    ) -> Result<SubmitTransactionAsyncResponse, Error> {
+        self.check_memo_required(&transaction).await?;
+
+        let tx = transaction.into_envelope().xdr_base64()?;
+        let url = self.server.url(&["transactions_async"]);
+
+        let mut query = HashMap::new();
+        query.insert("tx".to_string(), tx);
+
+        let res = send_request_async(&url, HttpMethod::POST, &query, &self.server).await?;
+        submit_transaction_async_response(&url, res)
+    }
+
+    pub async fn wait_for_transaction(
+        &self,
+        hash: &str,
+        timeout: Duration,
+    ) -> Result<Transaction, Error> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match self.load_transaction(hash).await {
+                Err(Error::NotFound(_)) => {}
+                result => return result,
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::Timeout);
+            }
+            tokio::time::sleep(TRANSACTION_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
+    pub fn build_fee_bump(
+        &self,
+        inner: TransactionSBase,
+        fee_source: &str,
+        base_fee: u32,
+    ) -> Result<FeeBumpTransaction, Error> {
+        self.server.build_fee_bump(inner, fee_source, base_fee)
+    }
+
+    pub async fn submit_fee_bump_transaction(
+        &self,
+        transaction: FeeBumpTransaction,
+    ) -> Result<SubmitTransactionResponse, Error> {
+        self.check_memo_required(transaction.inner_transaction())
+            .await?;
+
+        self.submit_envelope(transaction.into_envelope()).await
+    }
+
+    #[cfg(feature = "nacl")]
+    pub async fn resubmit_with_fee_bump(
+        &self,
+        inner: TransactionSBase,
+        fee_source: &Keypair,
+        percentile: u8,
+        network: &Network,
+    ) -> Result<SubmitTransactionResponse, Error> {
+        let fee_stats = self.fee_stats().await?;
+        let fee_bump = self
+            .server
+            .fee_bump_at_percentile(&fee_stats, inner, fee_source, percentile, network)?;
+
+        self.submit_fee_bump_transaction(fee_bump).await
+    }
+}
+
+impl AsyncServer {
+    async fn check_memo_required(&self, transaction: &TransactionSBase) -> Result<(), Error> {
+        if self.server.skip_memo_required_check(transaction) {
+            return Ok(());
+        }
+
+        for destination in memo_required_destinations(transaction) {
+            match self.load_account(&destination).await {
+                Ok(account) => check_account_memo_required(&account)?,
+                Err(Error::NotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn submit_envelope(
+        &self,
+        envelope: TransactionEnvelope,
+    ) -> Result<SubmitTransactionResponse, Error> {
+        let tx = envelope.xdr_base64()?;
+        let url = self.server.url(&["transactions"]);
+
+        let mut query = HashMap::new();
+        query.insert("tx".to_string(), tx);
+
+        api_call_async::<SubmitTransactionResponse>(url, HttpMethod::POST, &query, &self.server)
+            .await
+    }
+}
+
+impl From<Server> for AsyncServer {
+    fn from(server: Server) -> Self {
+        AsyncServer { server }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::transport::{HttpResponse, MockTransport};
+    use crate::types::{account_json, ledger_page_json};
+    use crate::CallBuilder;
+
+    fn mock_async_server(mock: &Arc<MockTransport>) -> AsyncServer {
+        let options = ServerOptions {
+            transport: Some(mock.clone()),
+            ..Default::default()
+        };
+
+        AsyncServer::new(String::from("https://horizon.stellar.org"), Some(options))
+            .expect("Cannot connect to insecure horizon server")
+    }
+
+    #[tokio::test]
+    async fn test_load_account_async() {
+        let account_id = "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM";
+        let mock = Arc::new(MockTransport::new());
+        mock.add_json(
+            &format!("https://horizon.stellar.org/accounts/{}", account_id),
+            &account_json("https://horizon.stellar.org", account_id, 1).to_string(),
+        );
+        let s = mock_async_server(&mock);
+
+        assert!(s.server().async_transport.get().is_none());
+
+        let account = s.load_account(account_id).await.unwrap();
+
+        assert_eq!(account.id, account.account_id);
+        assert!(s.server().async_transport.get().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_call_builder_async() {
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/ledgers",
+            &[("limit", "2")],
+            HttpResponse::new(200, &ledger_page_json(&[1, 2], 2, 2 << 32)),
+        );
+        let s = mock_async_server(&mock);
+
+        let ledgers = s.ledgers().limit(2).call_async().await.unwrap();
+
+        assert_eq!(ledgers._embedded.records.len(), 2);
+    }
+}
@@ -1,12 +1,13 @@
 use crate::{
     endpoints::horizon::Record,
     utils::{Direction, Endpoint},
+    Error,
 };
 
 pub trait CallBuilder<T> {
     fn cursor(&mut self, cursor: &str) -> &mut Self;
     fn order(&mut self, dir: Direction) -> &mut Self;
     fn limit(&mut self, limit_number: u8) -> &mut Self;
-    fn call(&self) -> Result<Record<T>, anyhow::Error>;
+    fn call(&self) -> Result<Record<T>, Error>;
     fn for_endpoint(&mut self, endpoint: Endpoint) -> &mut Self;
 }
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, Pages, Records, Server};
 use crate::types::{Asset, ClaimableBalance};
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct ClaimableBalanceCallbuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> ClaimableBalanceCallbuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -43,6 +44,34 @@ impl<'a> ClaimableBalanceCallbuilder<'a> {
 
         self
     }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<ClaimableBalance>, Error> {
+        api_call_async::<Record<ClaimableBalance>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, ClaimableBalance> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, ClaimableBalance> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/claimable_balances",
+        )
+    }
 }
 
 impl<'a> CallBuilder<ClaimableBalance> for ClaimableBalanceCallbuilder<'a> {
@@ -73,31 +102,75 @@ impl<'a> CallBuilder<ClaimableBalance> for ClaimableBalanceCallbuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<ClaimableBalance>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/claimable_balances",
-        );
+    fn call(&self) -> Result<Record<ClaimableBalance>, Error> {
+        let url = self.url();
 
         api_call::<Record<ClaimableBalance>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
+
+    const LSP_BALANCES: &str = r#"{
+        "_links": {
+            "self": {"href": "https://horizon.stellar.org/claimable_balances?cursor=&limit=1&order=asc"},
+            "next": {"href": "https://horizon.stellar.org/claimable_balances?cursor=41617649705713665-00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be&limit=1&order=asc"},
+            "prev": {"href": "https://horizon.stellar.org/claimable_balances?cursor=41617649705713665-00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be&limit=1&order=desc"}
+        },
+        "_embedded": {"records": [
+            {
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/claimable_balances/00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be"},
+                    "transactions": {"href": "https://horizon.stellar.org/claimable_balances/00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be/transactions{?cursor,limit,order}", "templated": true},
+                    "operations": {"href": "https://horizon.stellar.org/claimable_balances/00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be/operations{?cursor,limit,order}", "templated": true}
+                },
+                "id": "00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be",
+                "asset": "LSP:GAB7STHVD5BDH3EEYXPI3OM7PCS4V443PYB5FNT6CFGJVPDLMKDM24WK",
+                "amount": "1.0000000",
+                "sponsor": "GDCJIHD3623OCYNH65UUQC3NLG2D6YCNCDPZULRLCLOA76TBQRL6A3TF",
+                "last_modified_ledger": 41617649,
+                "last_modified_time": "2022-06-30T07:01:24Z",
+                "claimants": [
+                    {
+                        "destination": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                        "predicate": {"unconditional": true}
+                    },
+                    {
+                        "destination": "GDCJIHD3623OCYNH65UUQC3NLG2D6YCNCDPZULRLCLOA76TBQRL6A3TF",
+                        "predicate": {"not": {"abs_before": "2022-07-30T07:01:24Z", "abs_before_epoch": "1659164484"}}
+                    }
+                ],
+                "flags": {"clawback_enabled": false},
+                "paging_token": "41617649705713665-00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be"
+            }
+        ]}
+    }"#;
 
     #[test]
     fn test_claimable_balance_sponsor() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/claimable_balances",
+            &[
+                (
+                    "sponsor",
+                    "GDCJIHD3623OCYNH65UUQC3NLG2D6YCNCDPZULRLCLOA76TBQRL6A3TF",
+                ),
+                ("limit", "1"),
+            ],
+            HttpResponse::new(200, LSP_BALANCES),
+        );
+        let s = mock_server(&mock);
 
         let cbcb = ClaimableBalanceCallbuilder::new(&s)
             .sponsor("GDCJIHD3623OCYNH65UUQC3NLG2D6YCNCDPZULRLCLOA76TBQRL6A3TF")
@@ -105,18 +178,27 @@ mod tests {
             .call()
             .unwrap();
 
-        if let Some(x) = &cbcb._embedded.records[0].sponsor {
-            assert_eq!(
-                "GDCJIHD3623OCYNH65UUQC3NLG2D6YCNCDPZULRLCLOA76TBQRL6A3TF",
-                x
-            );
-        }
+        assert_eq!(
+            Some("GDCJIHD3623OCYNH65UUQC3NLG2D6YCNCDPZULRLCLOA76TBQRL6A3TF"),
+            cbcb._embedded.records[0].sponsor.as_deref()
+        );
     }
 
     #[test]
     fn test_claimable_balance_for_asset() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/claimable_balances",
+            &[
+                (
+                    "asset",
+                    "LSP:GAB7STHVD5BDH3EEYXPI3OM7PCS4V443PYB5FNT6CFGJVPDLMKDM24WK",
+                ),
+                ("limit", "1"),
+            ],
+            HttpResponse::new(200, LSP_BALANCES),
+        );
+        let s = mock_server(&mock);
 
         let lsp = Asset::new(
             String::from("LSP"),
@@ -1,27 +1,60 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, EventStream, Pages, Records, Server};
 use crate::types::Effect;
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct EffectCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> EffectCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
+
+    pub fn stream(&self) -> EventStream<'a, Effect> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Effect>, Error> {
+        api_call_async::<Record<Effect>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Effect> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Effect> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/effects",
+        )
+    }
 }
 
 impl<'a> CallBuilder<Effect> for EffectCallBuilder<'a> {
@@ -52,36 +85,80 @@ impl<'a> CallBuilder<Effect> for EffectCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<Effect>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/effects",
-        );
+    fn call(&self) -> Result<Record<Effect>, Error> {
+        let url = self.url();
 
         api_call::<Record<Effect>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn assets_horizon_test() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/effects",
+            &[("limit", "200")],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_links": {
+                        "self": {"href": "https://horizon.stellar.org/effects?cursor=&limit=200&order=asc"},
+                        "next": {"href": "https://horizon.stellar.org/effects?cursor=184717948121542657-2&limit=200&order=asc"},
+                        "prev": {"href": "https://horizon.stellar.org/effects?cursor=184717948121542657-1&limit=200&order=desc"}
+                    },
+                    "_embedded": {"records": [
+                        {
+                            "_links": {
+                                "operation": {"href": "https://horizon.stellar.org/operations/184717948121542657"},
+                                "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717948121542657-1"},
+                                "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717948121542657-1"}
+                            },
+                            "id": "0184717948121542657-0000000001",
+                            "paging_token": "184717948121542657-1",
+                            "account": "GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV",
+                            "type": "account_credited",
+                            "type_i": 2,
+                            "created_at": "2022-06-01T09:45:12Z",
+                            "asset_type": "native",
+                            "amount": "10.0000000"
+                        },
+                        {
+                            "_links": {
+                                "operation": {"href": "https://horizon.stellar.org/operations/184717948121542657"},
+                                "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717948121542657-2"},
+                                "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717948121542657-2"}
+                            },
+                            "id": "0184717948121542657-0000000002",
+                            "paging_token": "184717948121542657-2",
+                            "account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                            "type": "account_debited",
+                            "type_i": 3,
+                            "created_at": "2022-06-01T09:45:12Z",
+                            "asset_type": "native",
+                            "amount": "10.0000000"
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let mut ecb = EffectCallBuilder::new(&s);
 
         let effect_records = ecb.limit(200).call().unwrap();
 
-        assert_eq!(effect_records._embedded.records.len(), 200);
+        assert_eq!(effect_records._embedded.records.len(), 2);
     }
 }
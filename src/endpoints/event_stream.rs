@@ -0,0 +1,206 @@
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::thread;
+use std::time::Duration;
+
+use crate::endpoints::Server;
+use crate::transport::{stream_reconnect, HttpRequest};
+use crate::types::HttpMethod;
+use crate::Error;
+
+// Horizon asks clients to wait this long before reconnecting unless it sends a `retry:` hint
+const DEFAULT_RETRY: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Event {
+    pub id: Option<String>,
+    pub event: Option<String>,
+    pub data: Option<String>,
+    pub retry: Option<u64>,
+}
+
+/// Reads a single Server-Sent Event, returning `None` once the stream is closed.
+pub(crate) fn read_event<R: BufRead>(reader: &mut R) -> std::io::Result<Option<Event>> {
+    let mut event = Event::default();
+    let mut has_fields = false;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(if has_fields { Some(event) } else { None });
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            if has_fields {
+                return Ok(Some(event));
+            }
+            continue;
+        }
+
+        // Lines starting with a colon are comments, used by Horizon as keep-alives
+        if line.starts_with(':') {
+            continue;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        has_fields = true;
+        match field {
+            "id" => event.id = Some(String::from(value)),
+            "event" => event.event = Some(String::from(value)),
+            "retry" => event.retry = value.parse().ok(),
+            "data" => match &mut event.data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => event.data = Some(String::from(value)),
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Streams records from a Horizon endpoint using Server-Sent Events.
+///
+/// The stream reconnects whenever Horizon drops the connection, resuming from
+/// the id of the last received event and waiting for the `retry:` interval
+/// Horizon advertised. It only ends when the iterator is dropped.
+pub struct EventStream<'a, T> {
+    url: String,
+    query_params: HashMap<String, String>,
+    server: &'a Server,
+    reader: Option<Box<dyn BufRead + Send>>,
+    last_event_id: Option<String>,
+    retry: Duration,
+    connected_before: bool,
+    record: PhantomData<T>,
+}
+
+impl<'a, T> EventStream<'a, T> {
+    pub(crate) fn new(
+        url: String,
+        query_params: HashMap<String, String>,
+        server: &'a Server,
+    ) -> Self {
+        Self {
+            url,
+            query_params,
+            server,
+            reader: None,
+            last_event_id: None,
+            retry: DEFAULT_RETRY,
+            connected_before: false,
+            record: PhantomData,
+        }
+    }
+
+    fn connect(&mut self) -> Result<Box<dyn BufRead + Send>, Error> {
+        if let Some(id) = &self.last_event_id {
+            self.query_params
+                .insert(String::from("cursor"), String::from(id));
+        }
+
+        let req = HttpRequest::new(HttpMethod::GET, &self.url)
+            .with_query_params(&self.query_params)
+            .with_header("Accept", "text/event-stream")
+            .with_headers(self.server.options.request_headers());
+
+        self.server.transport.open_stream(&req)
+    }
+}
+
+impl<'a, T: DeserializeOwned> Iterator for EventStream<'a, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let reader = match &mut self.reader {
+                Some(reader) => reader,
+                None => {
+                    if self.connected_before {
+                        stream_reconnect(&self.url, self.retry, self.last_event_id.as_deref());
+                        thread::sleep(self.retry);
+                    }
+                    self.connected_before = true;
+
+                    match self.connect() {
+                        Ok(reader) => self.reader.insert(reader),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            };
+
+            let event = match read_event(reader) {
+                Ok(Some(event)) => event,
+                // The connection was closed or dropped, resume from the last event id
+                Ok(None) | Err(_) => {
+                    self.reader = None;
+                    continue;
+                }
+            };
+
+            if let Some(retry) = event.retry {
+                self.retry = Duration::from_millis(retry);
+            }
+
+            let data = match event.data {
+                Some(data) => data,
+                None => continue,
+            };
+
+            // Horizon greets new connections with "hello" and says "byebye" before closing them
+            if data == "\"hello\"" || data == "\"byebye\"" {
+                continue;
+            }
+
+            if let Some(id) = event.id {
+                self.last_event_id = Some(id);
+            }
+
+            return Some(serde_json::from_str::<T>(&data).map_err(Into::into));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_event() {
+        let mut reader = Cursor::new(
+            "retry: 1000\nevent: open\ndata: \"hello\"\n\n: keep-alive\n\nid: 1234-1\ndata: {\"a\":\ndata: 1}\n\n",
+        );
+
+        let hello = read_event(&mut reader).unwrap().unwrap();
+        assert_eq!(Some(1000), hello.retry);
+        assert_eq!(Some(String::from("open")), hello.event);
+        assert_eq!(Some(String::from("\"hello\"")), hello.data);
+
+        let record = read_event(&mut reader).unwrap().unwrap();
+        assert_eq!(Some(String::from("1234-1")), record.id);
+        assert_eq!(Some(String::from("{\"a\":\n1}")), record.data);
+
+        assert_eq!(None, read_event(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn test_read_event_without_trailing_blank_line() {
+        let mut reader = Cursor::new("id: 7\r\ndata: 42\r\n");
+
+        let event = read_event(&mut reader).unwrap().unwrap();
+
+        assert_eq!(Some(String::from("7")), event.id);
+        assert_eq!(Some(String::from("42")), event.data);
+        assert_eq!(None, read_event(&mut reader).unwrap());
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::types::Amount;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ResponseLink {
     pub href: String,
@@ -27,6 +29,7 @@ pub struct Record<T> {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Predicate {
+    pub unconditional: Option<bool>,
     pub and: Option<Vec<Predicate>>,
     pub or: Option<Vec<Predicate>>,
     pub not: Option<Box<Predicate>>,
@@ -42,13 +45,13 @@ pub struct Claimant {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Balance {
-    pub balance: String,
-    pub limit: Option<String>,
+    pub balance: Amount,
+    pub limit: Option<Amount>,
     pub asset_type: String,
     pub asset_code: Option<String>,
     pub asset_issuer: Option<String>,
-    pub buying_liabilities: Option<String>,
-    pub selling_liabilities: Option<String>,
+    pub buying_liabilities: Option<Amount>,
+    pub selling_liabilities: Option<Amount>,
     pub last_modified_ledger: Option<u64>,
     pub is_authorized: Option<bool>,
     pub is_authorized_to_maintain_liabilities: Option<bool>,
@@ -70,12 +73,6 @@ pub struct AssetBalances {
     pub unauthorized: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct PriceR {
-    pub numerator: u32,
-    pub denominator: u32,
-}
-
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PriceRShortHand<T> {
     pub n: T,
@@ -110,7 +107,7 @@ pub struct AccountSigner {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Reserve {
     pub asset: String,
-    pub amount: String,
+    pub amount: Amount,
     pub claimable_balance_id: Option<String>,
 }
 
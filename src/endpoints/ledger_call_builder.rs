@@ -1,27 +1,60 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, EventStream, Pages, Records, Server};
 use crate::types::Ledger;
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct LedgerCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> LedgerCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
+
+    pub fn stream(&self) -> EventStream<'a, Ledger> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Ledger>, Error> {
+        api_call_async::<Record<Ledger>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Ledger> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Ledger> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/ledgers",
+        )
+    }
 }
 
 impl<'a> CallBuilder<Ledger> for LedgerCallBuilder<'a> {
@@ -52,36 +85,149 @@ impl<'a> CallBuilder<Ledger> for LedgerCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<Ledger>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/ledgers",
-        );
+    fn call(&self) -> Result<Record<Ledger>, Error> {
+        let url = self.url();
 
         api_call::<Record<Ledger>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
+    use crate::types::{ledger_json, ledger_page_json};
 
     #[test]
     fn ledger_horizon_test() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/ledgers",
+            &[("limit", "200")],
+            HttpResponse::new(200, &ledger_page_json(&[1, 2, 3], 200, 3 << 32)),
+        );
+        let s = mock_server(&mock);
 
         let mut lcb = LedgerCallBuilder::new(&s);
 
         let ledger_records = lcb.limit(200).call().unwrap();
 
-        assert_eq!(ledger_records._embedded.records.len(), 200);
+        assert_eq!(ledger_records._embedded.records.len(), 3);
+    }
+    #[test]
+    fn ledger_records_follow_next_link() {
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/ledgers",
+            &[("limit", "3")],
+            HttpResponse::new(200, &ledger_page_json(&[1, 2, 3], 3, 3 << 32)),
+        )
+        .add_json(
+            "https://horizon.stellar.org/ledgers?cursor=12884901888",
+            &ledger_page_json(&[4, 5, 6], 3, 6 << 32),
+        )
+        .add_json(
+            "https://horizon.stellar.org/ledgers?cursor=25769803776",
+            &ledger_page_json(&[7, 8, 9], 3, 9 << 32),
+        );
+        let s = mock_server(&mock);
+
+        let ledgers = LedgerCallBuilder::new(&s)
+            .limit(3)
+            .records()
+            .max_records(7)
+            .collect::<Result<Vec<Ledger>, _>>()
+            .unwrap();
+
+        assert_eq!(ledgers.len(), 7);
+        assert_eq!(ledgers[2].sequence + 1, ledgers[3].sequence);
+        assert_eq!(
+            vec![
+                "https://horizon.stellar.org/ledgers",
+                "https://horizon.stellar.org/ledgers?cursor=12884901888&limit=3&order=asc",
+                "https://horizon.stellar.org/ledgers?cursor=25769803776&limit=3&order=asc",
+            ],
+            mock.requests()
+                .iter()
+                .map(|req| req.url.as_str())
+                .collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn ledger_pages_max_pages() {
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/ledgers",
+            &[("limit", "2")],
+            HttpResponse::new(200, &ledger_page_json(&[1, 2], 2, 2 << 32)),
+        )
+        .add_json(
+            "https://horizon.stellar.org/ledgers?cursor=8589934592",
+            &ledger_page_json(&[3, 4], 2, 4 << 32),
+        )
+        .add_json(
+            "https://horizon.stellar.org/ledgers?cursor=17179869184",
+            &ledger_page_json(&[5, 6], 2, 6 << 32),
+        );
+        let s = mock_server(&mock);
+
+        let pages = LedgerCallBuilder::new(&s)
+            .limit(2)
+            .pages()
+            .max_pages(2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1]._embedded.records.len(), 2);
+        assert_eq!(3, pages[1]._embedded.records[0].sequence);
+        assert_eq!(2, mock.requests().len());
+    }
+    #[test]
+    fn ledger_stream_test() {
+        // SSE data is a single line, so the ledgers are sent compacted
+        let event = |sequence: u32| {
+            let ledger: serde_json::Value = serde_json::from_str(&ledger_json(sequence)).unwrap();
+            format!("id: {}\ndata: {}\n\n", u64::from(sequence) << 32, ledger)
+        };
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response(
+            "https://horizon.stellar.org/ledgers",
+            HttpResponse::new(
+                200,
+                &format!(
+                    "retry: 0\ndata: \"hello\"\n\n{}data: \"byebye\"\n\n",
+                    event(3)
+                ),
+            ),
+        )
+        .add_response(
+            "https://horizon.stellar.org/ledgers?cursor=12884901888",
+            HttpResponse::new(200, &event(4)),
+        );
+        let s = mock_server(&mock);
+
+        let ledgers = LedgerCallBuilder::new(&s)
+            .stream()
+            .take(2)
+            .collect::<Result<Vec<Ledger>, _>>()
+            .unwrap();
+
+        assert_eq!(ledgers[0].sequence + 1, ledgers[1].sequence);
+        // The stream reconnected once Horizon closed it, resuming after ledger 3
+        let requests = mock.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            Some(&String::from("12884901888")),
+            requests[1].query_params.get("cursor")
+        );
     }
 }
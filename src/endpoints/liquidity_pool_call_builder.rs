@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, Pages, Records, Server};
 use crate::types::{Asset, LiquidityPool};
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct LiquidityPoolCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> LiquidityPoolCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -35,6 +36,34 @@ impl<'a> LiquidityPoolCallBuilder<'a> {
 
         self
     }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<LiquidityPool>, Error> {
+        api_call_async::<Record<LiquidityPool>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, LiquidityPool> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, LiquidityPool> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/liquidity_pools",
+        )
+    }
 }
 
 impl<'a> CallBuilder<LiquidityPool> for LiquidityPoolCallBuilder<'a> {
@@ -65,45 +94,84 @@ impl<'a> CallBuilder<LiquidityPool> for LiquidityPoolCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<LiquidityPool>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/liquidity_pools",
-        );
+    fn call(&self) -> Result<Record<LiquidityPool>, Error> {
+        let url = self.url();
 
         api_call::<Record<LiquidityPool>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
+
+    const YXLM_YUSDC_POOLS: &str = r#"{
+        "_links": {
+            "self": {"href": "https://horizon.stellar.org/liquidity_pools?cursor=&limit=10&order=asc&reserves=yXLM%3AGARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55%2CyUSDC%3AGDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF"},
+            "next": {"href": "https://horizon.stellar.org/liquidity_pools?cursor=41617649705713665-2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355&limit=10&order=asc&reserves=yXLM%3AGARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55%2CyUSDC%3AGDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF"},
+            "prev": {"href": "https://horizon.stellar.org/liquidity_pools?cursor=41617649705713665-2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355&limit=10&order=desc&reserves=yXLM%3AGARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55%2CyUSDC%3AGDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF"}
+        },
+        "_embedded": {"records": [
+            {
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/liquidity_pools/2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355"},
+                    "transactions": {"href": "https://horizon.stellar.org/liquidity_pools/2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355/transactions{?cursor,limit,order}", "templated": true},
+                    "operations": {"href": "https://horizon.stellar.org/liquidity_pools/2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355/operations{?cursor,limit,order}", "templated": true}
+                },
+                "id": "2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355",
+                "paging_token": "41617649705713665-2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355",
+                "fee_bp": 30,
+                "type": "constant_product",
+                "total_trustlines": "245",
+                "total_shares": "5290711.7398262",
+                "reserves": [
+                    {"asset": "yXLM:GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55", "amount": "24306478.6719374"},
+                    {"asset": "yUSDC:GDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF", "amount": "2659917.1451606"}
+                ],
+                "last_modified_ledger": 41617649,
+                "last_modified_time": "2022-06-30T07:01:24Z"
+            }
+        ]}
+    }"#;
 
     #[test]
     fn test_limit_liquidity_pools() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/liquidity_pools",
+            &[("limit", "200")],
+            HttpResponse::new(200, YXLM_YUSDC_POOLS),
+        );
+        let s = mock_server(&mock);
 
         let mut lpcb = LiquidityPoolCallBuilder::new(&s);
 
         let records = lpcb.limit(200).call().unwrap();
 
-        assert_eq!(records._embedded.records.len(), 200);
+        assert_eq!(records._embedded.records.len(), 1);
     }
 
     #[test]
     fn test_liquidity_pools_for_assets() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
-
         let y_xlm_str = "yXLM:GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55";
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/liquidity_pools",
+            &[(
+                "reserves",
+                "yXLM:GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55,yUSDC:GDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF",
+            )],
+            HttpResponse::new(200, YXLM_YUSDC_POOLS),
+        );
+        let s = mock_server(&mock);
 
         let y_xlm = Asset::new(
             String::from("yXLM"),
@@ -1,14 +1,18 @@
 mod account_call_builder;
 mod asset_call_builder;
+#[cfg(feature = "async")]
+mod async_server;
 mod call_builder;
 mod claimable_balance_call_builder;
 mod effect_call_builder;
+mod event_stream;
 pub mod horizon;
 mod ledger_call_builder;
 mod liquidity_pool_call_builder;
 mod offer_call_builder;
 mod operation_call_builder;
 mod order_book_call_builder;
+mod pages;
 mod payment_call_builder;
 mod server;
 mod strict_receive_call_builder;
@@ -20,16 +24,20 @@ mod transaction_call_builder;
 
 pub use account_call_builder::AccountCallBuilder;
 pub use asset_call_builder::AssetCallBuilder;
+#[cfg(feature = "async")]
+pub use async_server::AsyncServer;
 pub use call_builder::CallBuilder;
 pub use claimable_balance_call_builder::ClaimableBalanceCallbuilder;
 pub use effect_call_builder::EffectCallBuilder;
+pub use event_stream::EventStream;
 pub use ledger_call_builder::LedgerCallBuilder;
 pub use liquidity_pool_call_builder::LiquidityPoolCallBuilder;
 pub use offer_call_builder::OfferCallBuilder;
 pub use operation_call_builder::OperationCallBuilder;
 pub use order_book_call_builder::OrderBookCallBuilder;
+pub use pages::{Pages, Records};
 pub use payment_call_builder::PaymentCallBuilder;
-pub use server::Server;
+pub use server::{Server, ServerOptions, SubmitTransactionOptions, Timebounds};
 pub use strict_receive_call_builder::StrictReceiveCallBuilder;
 pub use strict_send_call_builder::StrictSendCallBuilder;
 pub use toml_resolver::StellarTomlResolver;
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, EventStream, Pages, Records, Server};
 use crate::types::{Asset, Offer};
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct OfferCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> OfferCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         OfferCallBuilder {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -50,6 +51,38 @@ impl<'a> OfferCallBuilder<'a> {
 
         self
     }
+
+    pub fn stream(&self) -> EventStream<'a, Offer> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Offer>, Error> {
+        api_call_async::<Record<Offer>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Offer> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Offer> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/offers"
+        )
+    }
 }
 
 impl<'a> CallBuilder<Offer> for OfferCallBuilder<'a> {
@@ -80,43 +113,109 @@ impl<'a> CallBuilder<Offer> for OfferCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<Offer>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/offers"
-        );
+    fn call(&self) -> Result<Record<Offer>, Error> {
+        let url = self.url();
 
         api_call::<Record<Offer>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
+
+    const YXLM_OFFERS: &str = r#"{
+        "_links": {
+            "self": {"href": "https://horizon.stellar.org/offers?cursor=&limit=2&order=asc"},
+            "next": {"href": "https://horizon.stellar.org/offers?cursor=1015718040&limit=2&order=asc"},
+            "prev": {"href": "https://horizon.stellar.org/offers?cursor=1015718039&limit=2&order=desc"}
+        },
+        "_embedded": {"records": [
+            {
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/offers/1015718039"},
+                    "offer_maker": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM"}
+                },
+                "id": "1015718039",
+                "paging_token": "1015718039",
+                "seller": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "selling": {
+                    "asset_type": "credit_alphanum4",
+                    "asset_code": "yXLM",
+                    "asset_issuer": "GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55"
+                },
+                "buying": {"asset_type": "native"},
+                "amount": "1250.0000000",
+                "price_r": {"n": 100, "d": 99},
+                "price": "1.0101010",
+                "last_modified_ledger": 41617649,
+                "last_modified_time": "2022-06-30T07:01:24Z"
+            },
+            {
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/offers/1015718040"},
+                    "offer_maker": {"href": "https://horizon.stellar.org/accounts/GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN"}
+                },
+                "id": "1015718040",
+                "paging_token": "1015718040",
+                "seller": "GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN",
+                "selling": {
+                    "asset_type": "credit_alphanum4",
+                    "asset_code": "yXLM",
+                    "asset_issuer": "GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55"
+                },
+                "buying": {"asset_type": "native"},
+                "amount": "80.5000000",
+                "price_r": {"n": 101, "d": 100},
+                "price": "1.0100000",
+                "last_modified_ledger": 41617702,
+                "last_modified_time": "2022-06-30T07:06:31Z",
+                "sponsor": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM"
+            }
+        ]}
+    }"#;
 
     #[test]
     fn test_offer_call_builder() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/offers",
+            &[("limit", "200")],
+            HttpResponse::new(200, YXLM_OFFERS),
+        );
+        let s = mock_server(&mock);
 
         let mut ocb = OfferCallBuilder::new(&s);
 
         let offer_records = ocb.limit(200).call().unwrap();
 
-        assert_eq!(offer_records._embedded.records.len(), 200);
+        assert_eq!(offer_records._embedded.records.len(), 2);
     }
 
     #[test]
     fn test_offer_call_builder_selling_buying() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/offers",
+            &[
+                (
+                    "selling",
+                    "yXLM:GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55",
+                ),
+                ("buying", "native"),
+                ("limit", "2"),
+            ],
+            HttpResponse::new(200, YXLM_OFFERS),
+        );
+        let s = mock_server(&mock);
 
         let y_xlm = Asset::new(
             String::from("yXLM"),
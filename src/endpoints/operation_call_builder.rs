@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, EventStream, Pages, Records, Server};
 use crate::types::Operation;
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct OperationCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> OperationCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -29,6 +30,38 @@ impl<'a> OperationCallBuilder<'a> {
 
         self
     }
+
+    pub fn stream(&self) -> EventStream<'a, Operation> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Operation>, Error> {
+        api_call_async::<Record<Operation>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Operation> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Operation> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/operations",
+        )
+    }
 }
 
 impl<'a> CallBuilder<Operation> for OperationCallBuilder<'a> {
@@ -59,31 +92,84 @@ impl<'a> CallBuilder<Operation> for OperationCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<Operation>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/operations",
-        );
+    fn call(&self) -> Result<Record<Operation>, Error> {
+        let url = self.url();
 
         api_call::<Record<Operation>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn limit_operation_call_builder() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/operations",
+            &[("limit", "200")],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_links": {
+                        "self": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/operations?cursor=&limit=200&order=asc"},
+                        "next": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/operations?cursor=184717952416509953&limit=200&order=asc"},
+                        "prev": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/operations?cursor=184717948121542657&limit=200&order=desc"}
+                    },
+                    "_embedded": {"records": [
+                        {
+                            "_links": {
+                                "self": {"href": "https://horizon.stellar.org/operations/184717948121542657"},
+                                "transaction": {"href": "https://horizon.stellar.org/transactions/5f0c2a4dcbc6b7d8f9bb2ebfa6b4d0b4a8d1e5b5e1b4c2f6a7d3e9f0c1b2a3d4"},
+                                "effects": {"href": "https://horizon.stellar.org/operations/184717948121542657/effects"},
+                                "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717948121542657"},
+                                "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717948121542657"}
+                            },
+                            "id": "184717948121542657",
+                            "paging_token": "184717948121542657",
+                            "transaction_successful": true,
+                            "source_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                            "type": "payment",
+                            "type_i": 1,
+                            "created_at": "2022-06-01T09:45:12Z",
+                            "transaction_hash": "5f0c2a4dcbc6b7d8f9bb2ebfa6b4d0b4a8d1e5b5e1b4c2f6a7d3e9f0c1b2a3d4",
+                            "asset_type": "native",
+                            "from": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                            "to": "GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV",
+                            "amount": "10.0000000"
+                        },
+                        {
+                            "_links": {
+                                "self": {"href": "https://horizon.stellar.org/operations/184717952416509953"},
+                                "transaction": {"href": "https://horizon.stellar.org/transactions/0b6e4a1f3c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f"},
+                                "effects": {"href": "https://horizon.stellar.org/operations/184717952416509953/effects"},
+                                "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717952416509953"},
+                                "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717952416509953"}
+                            },
+                            "id": "184717952416509953",
+                            "paging_token": "184717952416509953",
+                            "transaction_successful": true,
+                            "source_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                            "type": "bump_sequence",
+                            "type_i": 11,
+                            "created_at": "2022-06-01T09:45:18Z",
+                            "transaction_hash": "0b6e4a1f3c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f",
+                            "bump_to": "129664371176506180"
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let mut ocb = OperationCallBuilder::new(&s);
 
@@ -95,6 +181,6 @@ mod tests {
             .call()
             .unwrap();
 
-        assert_eq!(op_records._embedded.records.len(), 200);
+        assert_eq!(op_records._embedded.records.len(), 2);
     }
 }
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::Server;
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{EventStream, Server};
 use crate::types::{Asset, OrderBook};
 use crate::utils::Endpoint;
+use crate::Error;
 
 #[derive(Debug)]
 pub struct OrderBookCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> OrderBookCallBuilder<'a> {
     pub fn new(s: &'a Server, selling: Asset, buying: Asset) -> Self {
         let mut new_self = Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         };
 
         new_self
@@ -40,31 +41,83 @@ impl<'a> OrderBookCallBuilder<'a> {
         self
     }
 
-    pub fn call(&self) -> Result<OrderBook, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/order_book"
-        );
+    pub fn call(&self) -> Result<OrderBook, Error> {
+        let url = self.url();
 
         api_call::<OrderBook>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
+        )
+    }
+
+    pub fn stream(&self) -> EventStream<'a, OrderBook> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<OrderBook, Error> {
+        api_call_async::<OrderBook>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/order_book"
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn test_order_book() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/order_book",
+            &[
+                ("selling_asset_type", "native"),
+                ("buying_asset_type", "credit_alphanum4"),
+                ("buying_asset_code", "BAT"),
+                (
+                    "buying_asset_issuer",
+                    "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR",
+                ),
+                ("limit", "1"),
+            ],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "bids": [
+                        {"price_r": {"n": 2, "d": 25}, "price": "0.0800000", "amount": "31.2500000"}
+                    ],
+                    "asks": [
+                        {"price_r": {"n": 1, "d": 10}, "price": "0.1000000", "amount": "120.0000000"}
+                    ],
+                    "base": {"asset_type": "native"},
+                    "counter": {
+                        "asset_type": "credit_alphanum4",
+                        "asset_code": "BAT",
+                        "asset_issuer": "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR"
+                    }
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let native = Asset::native();
         let bat = Asset::new(
@@ -0,0 +1,126 @@
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::api_call::api_call;
+use crate::endpoints::{horizon::Record, Server};
+use crate::types::HttpMethod;
+use crate::Error;
+
+/// Lazily walks a Horizon collection page by page by following `_links.next`.
+///
+/// Iteration stops once Horizon returns an empty page, a page without a `next`
+/// link, an error, or when the optional `max_pages` limit is reached.
+#[derive(Debug)]
+pub struct Pages<'a, T> {
+    url: Option<String>,
+    query_params: HashMap<String, String>,
+    server: &'a Server,
+    max_pages: Option<usize>,
+    fetched: usize,
+    record: PhantomData<T>,
+}
+
+impl<'a, T> Pages<'a, T> {
+    pub(crate) fn new(
+        url: String,
+        query_params: HashMap<String, String>,
+        server: &'a Server,
+    ) -> Self {
+        Self {
+            url: Some(url),
+            query_params,
+            server,
+            max_pages: None,
+            fetched: 0,
+            record: PhantomData,
+        }
+    }
+
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+
+        self
+    }
+
+    pub fn records(self) -> Records<'a, T> {
+        Records {
+            pages: self,
+            buffer: Vec::new().into_iter(),
+            max_records: None,
+            yielded: 0,
+        }
+    }
+}
+
+impl<'a, T: DeserializeOwned> Iterator for Pages<'a, T> {
+    type Item = Result<Record<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_pages.is_some_and(|max| self.fetched >= max) {
+            return None;
+        }
+
+        let url = self.url.take()?;
+        let page =
+            match api_call::<Record<T>>(url, HttpMethod::GET, &self.query_params, self.server) {
+                Ok(page) => page,
+                Err(e) => return Some(Err(e)),
+            };
+
+        // The next link already carries the cursor and every other query param
+        self.query_params.clear();
+        self.fetched += 1;
+
+        if page._embedded.records.is_empty() {
+            return None;
+        }
+
+        self.url = page
+            ._links
+            .as_ref()
+            .and_then(|links| links.next.as_ref())
+            .map(|next| next.href.clone());
+
+        Some(Ok(page))
+    }
+}
+
+/// Flattens [`Pages`] into the individual records of a Horizon collection.
+#[derive(Debug)]
+pub struct Records<'a, T> {
+    pages: Pages<'a, T>,
+    buffer: std::vec::IntoIter<T>,
+    max_records: Option<usize>,
+    yielded: usize,
+}
+
+impl<'a, T> Records<'a, T> {
+    pub fn max_records(mut self, max_records: usize) -> Self {
+        self.max_records = Some(max_records);
+
+        self
+    }
+}
+
+impl<'a, T: DeserializeOwned> Iterator for Records<'a, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_records.is_some_and(|max| self.yielded >= max) {
+            return None;
+        }
+
+        loop {
+            if let Some(record) = self.buffer.next() {
+                self.yielded += 1;
+                return Some(Ok(record));
+            }
+
+            match self.pages.next()? {
+                Ok(page) => self.buffer = page._embedded.records.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
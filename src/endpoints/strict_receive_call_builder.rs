@@ -1,16 +1,18 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, Pages, Records, Server};
 use crate::types::{Asset, StrictPath, StrictPathSource};
 use crate::utils::{Direction, Endpoint};
 use crate::CallBuilder;
+use crate::Error;
 
 #[derive(Debug)]
 pub struct StrictReceiveCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> CallBuilder<StrictPath> for StrictReceiveCallBuilder<'a> {
@@ -39,13 +41,13 @@ impl<'a> CallBuilder<StrictPath> for StrictReceiveCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<StrictPath>, anyhow::Error> {
-        let url = format!("{}{}", &self.server_url, "/paths/strict-receive");
+    fn call(&self) -> Result<Record<StrictPath>, Error> {
+        let url = self.url();
         api_call::<Record<StrictPath>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
@@ -58,9 +60,8 @@ impl<'a> StrictReceiveCallBuilder<'a> {
         destination_amount: &str,
     ) -> Self {
         let mut new_self = Self {
-            server_url: &s.server_url,
+            server: s,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         };
 
         match source {
@@ -88,16 +89,70 @@ impl<'a> StrictReceiveCallBuilder<'a> {
 
         new_self
     }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<StrictPath>, Error> {
+        api_call_async::<Record<StrictPath>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, StrictPath> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, StrictPath> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!("{}{}", &self.server.server_url, "/paths/strict-receive")
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn test_strict_receive() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/paths/strict-receive",
+            &[
+                (
+                    "source_account",
+                    "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR",
+                ),
+                ("destination_asset_type", "native"),
+                ("destination_amount", "20"),
+                ("limit", "1"),
+            ],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_embedded": {"records": [
+                        {
+                            "source_asset_type": "credit_alphanum4",
+                            "source_asset_code": "BAT",
+                            "source_asset_issuer": "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR",
+                            "source_amount": "1.6000000",
+                            "destination_asset_type": "native",
+                            "destination_amount": "20.0000000",
+                            "path": []
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let native = Asset::native();
         let _bat = Asset::new(
@@ -105,7 +160,7 @@ mod tests {
             String::from("GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR"),
         );
 
-        let _ocb = StrictReceiveCallBuilder::new(
+        let ocb = StrictReceiveCallBuilder::new(
             &s,
             &StrictPathSource::Account(
                 "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR".to_string(),
@@ -116,5 +171,7 @@ mod tests {
         .limit(1)
         .call()
         .unwrap();
+
+        assert_eq!("1.6000000", ocb._embedded.records[0].source_amount);
     }
 }
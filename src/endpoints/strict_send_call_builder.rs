@@ -1,16 +1,18 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, Pages, Records, Server};
 use crate::types::{Asset, StrictPath, StrictPathSource};
 use crate::utils::{Direction, Endpoint};
 use crate::CallBuilder;
+use crate::Error;
 
 #[derive(Debug)]
 pub struct StrictSendCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> CallBuilder<StrictPath> for StrictSendCallBuilder<'a> {
@@ -39,13 +41,13 @@ impl<'a> CallBuilder<StrictPath> for StrictSendCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<StrictPath>, anyhow::Error> {
-        let url = format!("{}{}", &self.server_url, "/paths/strict-send");
+    fn call(&self) -> Result<Record<StrictPath>, Error> {
+        let url = self.url();
         api_call::<Record<StrictPath>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
@@ -58,9 +60,8 @@ impl<'a> StrictSendCallBuilder<'a> {
         source_amount: &str,
     ) -> Self {
         let mut new_self = Self {
-            server_url: &s.server_url,
+            server: s,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         };
 
         match destination {
@@ -88,24 +89,86 @@ impl<'a> StrictSendCallBuilder<'a> {
 
         new_self
     }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<StrictPath>, Error> {
+        api_call_async::<Record<StrictPath>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, StrictPath> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, StrictPath> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!("{}{}", &self.server.server_url, "/paths/strict-send")
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn test_strict_send() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/paths/strict-send",
+            &[
+                (
+                    "destination_account",
+                    "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR",
+                ),
+                ("source_asset_type", "native"),
+                ("source_amount", "20"),
+                ("limit", "1"),
+            ],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_embedded": {"records": [
+                        {
+                            "source_asset_type": "native",
+                            "source_amount": "20.0000000",
+                            "destination_asset_type": "credit_alphanum4",
+                            "destination_asset_code": "BAT",
+                            "destination_asset_issuer": "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR",
+                            "destination_amount": "1.5873016",
+                            "path": [
+                                {
+                                    "asset_type": "credit_alphanum4",
+                                    "asset_code": "USDC",
+                                    "asset_issuer": "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+                                }
+                            ]
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let native = Asset::native();
         let rbt_issuer = String::from("GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR");
 
-        let _ocb =
+        let ocb =
             StrictSendCallBuilder::new(&s, &StrictPathSource::Account(rbt_issuer), &native, "20")
                 .limit(1)
                 .call()
                 .unwrap();
+
+        assert_eq!(1, ocb._embedded.records[0].path.len());
     }
 }
@@ -1,5 +1,11 @@
 #![allow(non_snake_case)]
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::endpoints::ServerOptions;
+use crate::transport::{HttpRequest, HttpTransport, UreqTransport};
+use crate::types::HttpMethod;
+use crate::Error;
 
 pub struct StellarTomlResolver;
 
@@ -31,15 +37,6 @@ pub struct TomlPrincipals {
     pub verification_photo_hash: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct TomlValidators {
-    pub ALIAS: Option<String>,
-    pub DISPLAY_NAME: Option<String>,
-    pub HOST: Option<String>,
-    pub PUBLIC_KEY: Option<String>,
-    pub HISTORY: Option<String>,
-}
-
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TomlCurrencies {
     pub code: Option<String>,
@@ -77,26 +74,114 @@ pub struct StellarToml {
 }
 
 impl StellarTomlResolver {
-    pub fn resolve(domain: &str) -> Result<StellarToml, anyhow::Error> {
+    pub fn resolve(domain: &str) -> Result<StellarToml, Error> {
+        Self::resolve_with_options(domain, &ServerOptions::default())
+    }
+
+    pub fn resolve_with_transport(
+        domain: &str,
+        transport: &dyn HttpTransport,
+    ) -> Result<StellarToml, Error> {
+        Self::fetch(domain, transport, &ServerOptions::default())
+    }
+
+    /// Resolves with the transport, app and custom headers of `options`, or without a
+    /// transport with its timeouts, proxy and root certificates. The auth token is
+    /// meant for Horizon and is not sent to `domain`.
+    pub fn resolve_with_options(
+        domain: &str,
+        options: &ServerOptions,
+    ) -> Result<StellarToml, Error> {
+        let transport: Arc<dyn HttpTransport> = match &options.transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(UreqTransport::from_options(options)?),
+        };
+
+        Self::fetch(domain, transport.as_ref(), options)
+    }
+
+    fn fetch(
+        domain: &str,
+        transport: &dyn HttpTransport,
+        options: &ServerOptions,
+    ) -> Result<StellarToml, Error> {
         let url = format!("https://{}/.well-known/stellar.toml", domain);
+        let req = HttpRequest::new(HttpMethod::GET, &url).with_headers(options.client_headers());
 
-        let res = ureq::get(&url).call()?;
-        let res_str = res.into_string()?;
+        let res = transport.send(&req)?;
+        if !res.is_success() {
+            return Err(Error::from_response(&url, res));
+        }
 
-        Ok(toml::from_str::<StellarToml>(&res_str)?)
+        Ok(toml::from_str::<StellarToml>(&res.body)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transport::{HttpResponse, MockTransport};
 
     #[test]
     fn test_toml_resolve() {
-        let rabet_toml = StellarTomlResolver::resolve("rabet.io").unwrap();
+        let mock = MockTransport::new();
+        mock.add_response(
+            "https://rabet.io/.well-known/stellar.toml",
+            HttpResponse::new(
+                200,
+                r#"VERSION = "2.0.0"
+NETWORK_PASSPHRASE = "Public Global Stellar Network ; September 2015"
+ACCOUNTS = ["GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM"]
 
-        if let Some(d) = rabet_toml.DOCUMENTATION {
-            assert_eq!(Some(String::from("Rabet")), d.ORG_DBA);
-        }
+[DOCUMENTATION]
+ORG_NAME = "Rabet"
+ORG_DBA = "Rabet"
+ORG_URL = "https://rabet.io"
+ORG_TWITTER = "rabetofficial"
+"#,
+            ),
+        );
+
+        let rabet_toml = StellarTomlResolver::resolve_with_transport("rabet.io", &mock).unwrap();
+
+        assert_eq!(
+            Some(String::from("Rabet")),
+            rabet_toml.DOCUMENTATION.unwrap().ORG_DBA
+        );
+    }
+
+    #[test]
+    fn test_toml_resolve_with_options() {
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response(
+            "https://example.com/.well-known/stellar.toml",
+            HttpResponse::new(200, "VERSION = \"2.0.0\""),
+        );
+        let options = ServerOptions {
+            app_name: Some(String::from("wallet")),
+            auth_token: Some(String::from("secret")),
+            transport: Some(mock.clone()),
+            ..Default::default()
+        };
+
+        let toml = StellarTomlResolver::resolve_with_options("example.com", &options).unwrap();
+
+        assert_eq!(Some(String::from("2.0.0")), toml.VERSION);
+        let headers = &mock.requests()[0].headers;
+        assert!(headers.contains(&(String::from("X-App-Name"), String::from("wallet"))));
+        assert!(headers.iter().all(|(name, _)| name != "Authorization"));
+    }
+
+    #[test]
+    fn test_toml_resolve_with_options_builds_transport() {
+        let options = ServerOptions {
+            root_certificates: Some(b"not a certificate".to_vec()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            StellarTomlResolver::resolve_with_options("example.com", &options),
+            Err(Error::InvalidCertificate(_))
+        ));
     }
 }
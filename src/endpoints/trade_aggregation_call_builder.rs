@@ -1,26 +1,27 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, Pages, Records, Server};
 use crate::types::{Asset, TradeAggregation};
 use crate::utils::{Direction, Endpoint};
 use crate::CallBuilder;
+use crate::Error;
 
 #[derive(Debug)]
 pub struct TradeAggregationCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> TradeAggregationCallBuilder<'a> {
     pub fn new(s: &'a Server, base: &Asset, counter: &Asset, resolution: &str) -> Self {
         let mut new_self = Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         };
 
         new_self
@@ -35,6 +36,29 @@ impl<'a> TradeAggregationCallBuilder<'a> {
 
         new_self
     }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<TradeAggregation>, Error> {
+        api_call_async::<Record<TradeAggregation>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, TradeAggregation> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, TradeAggregation> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!("{}{}", &self.server.server_url, "/trade_aggregations")
+    }
 }
 
 impl<'a> CallBuilder<TradeAggregation> for TradeAggregationCallBuilder<'a> {
@@ -65,25 +89,68 @@ impl<'a> CallBuilder<TradeAggregation> for TradeAggregationCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<TradeAggregation>, anyhow::Error> {
-        let url = format!("{}{}", &self.server_url, "/trade_aggregations");
+    fn call(&self) -> Result<Record<TradeAggregation>, Error> {
+        let url = self.url();
         api_call::<Record<TradeAggregation>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn test_trade_aggregation() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/trade_aggregations",
+            &[
+                ("base_asset_type", "native"),
+                ("counter_asset_type", "credit_alphanum4"),
+                ("counter_asset_code", "BAT"),
+                (
+                    "counter_asset_issuer",
+                    "GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR",
+                ),
+                ("resolution", "60000"),
+                ("limit", "1"),
+            ],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_links": {
+                        "self": {"href": "https://horizon.stellar.org/trade_aggregations?base_asset_type=native&counter_asset_code=BAT&counter_asset_issuer=GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR&counter_asset_type=credit_alphanum4&limit=1&resolution=60000"},
+                        "next": {"href": "https://horizon.stellar.org/trade_aggregations?base_asset_type=native&counter_asset_code=BAT&counter_asset_issuer=GBDEVU63Y6NTHJQQZIKVTC23NWLQVP3WJ2RI2OTSJTNYOIGICST6DUXR&counter_asset_type=credit_alphanum4&limit=1&resolution=60000&start_time=1447818480000"}
+                    },
+                    "_embedded": {"records": [
+                        {
+                            "timestamp": "1447818420000",
+                            "trade_count": "2",
+                            "base_volume": "20.0000000",
+                            "counter_volume": "4.0000000",
+                            "avg": "0.2000000",
+                            "high": "0.2000000",
+                            "high_r": {"n": "1", "d": "5"},
+                            "low": "0.2000000",
+                            "low_r": {"n": "1", "d": "5"},
+                            "open": "0.2000000",
+                            "open_r": {"n": "1", "d": "5"},
+                            "close": "0.2000000",
+                            "close_r": {"n": "1", "d": "5"}
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let native = Asset::native();
         let bat = Asset::new(
@@ -1,24 +1,25 @@
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, EventStream, Pages, Records, Server};
 use crate::types::{Asset, Trade};
 use crate::utils::{Direction, Endpoint, TradeType};
+use crate::Error;
 use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct TradeCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> TradeCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -44,22 +45,49 @@ impl<'a> TradeCallBuilder<'a> {
 
         self
     }
-}
 
-impl<'a> CallBuilder<Trade> for TradeCallBuilder<'a> {
-    fn call(&self) -> Result<Record<Trade>, anyhow::Error> {
-        let url = format!(
+    pub fn stream(&self) -> EventStream<'a, Trade> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Trade>, Error> {
+        api_call_async::<Record<Trade>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Trade> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Trade> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
             "{}{}{}",
-            &self.server_url,
+            &self.server.server_url,
             self.endpoint.as_str(),
             "/trades",
-        );
+        )
+    }
+}
+
+impl<'a> CallBuilder<Trade> for TradeCallBuilder<'a> {
+    fn call(&self) -> Result<Record<Trade>, Error> {
+        let url = self.url();
 
         api_call::<Record<Trade>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 
@@ -93,24 +121,103 @@ impl<'a> CallBuilder<Trade> for TradeCallBuilder<'a> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
+
+    const YUSDC_TRADES: &str = r#"{
+        "_links": {
+            "self": {"href": "https://horizon.stellar.org/trades?cursor=&limit=2&order=asc"},
+            "next": {"href": "https://horizon.stellar.org/trades?cursor=178454385453166593-1&limit=2&order=asc"},
+            "prev": {"href": "https://horizon.stellar.org/trades?cursor=178454385453166593-0&limit=2&order=desc"}
+        },
+        "_embedded": {"records": [
+            {
+                "_links": {
+                    "self": {"href": ""},
+                    "base": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM"},
+                    "counter": {"href": "https://horizon.stellar.org/accounts/GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN"},
+                    "operation": {"href": "https://horizon.stellar.org/operations/178454385453166593"}
+                },
+                "id": "178454385453166593-0",
+                "paging_token": "178454385453166593-0",
+                "ledger_close_time": "2022-06-23T10:21:07Z",
+                "trade_type": "orderbook",
+                "base_offer_id": "1015718039",
+                "base_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "base_amount": "100.0000000",
+                "base_asset_type": "native",
+                "counter_offer_id": "4790883838934622209",
+                "counter_account": "GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN",
+                "counter_amount": "11.0000000",
+                "counter_asset_type": "credit_alphanum12",
+                "counter_asset_code": "yUSDC",
+                "counter_asset_issuer": "GDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF",
+                "base_is_seller": true,
+                "price": {"n": "11", "d": "100"}
+            },
+            {
+                "_links": {
+                    "self": {"href": ""},
+                    "base": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM"},
+                    "counter": {"href": "https://horizon.stellar.org/liquidity_pools/2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355"},
+                    "operation": {"href": "https://horizon.stellar.org/operations/178454385453166593"}
+                },
+                "id": "178454385453166593-1",
+                "paging_token": "178454385453166593-1",
+                "ledger_close_time": "2022-06-23T10:21:07Z",
+                "trade_type": "liquidity_pool",
+                "liquidity_pool_fee_bp": 30,
+                "base_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "base_amount": "50.0000000",
+                "base_asset_type": "native",
+                "counter_liquidity_pool_id": "2c0bfa623845dd101cbf074a1ca1ae4b2458cc8d0104ad65939ebe2cd9054355",
+                "counter_amount": "5.4945054",
+                "counter_asset_type": "credit_alphanum12",
+                "counter_asset_code": "yUSDC",
+                "counter_asset_issuer": "GDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF",
+                "base_is_seller": true,
+                "price": {"n": "50000000", "d": "454545454"}
+            }
+        ]}
+    }"#;
 
     #[test]
     fn limit_trade_call_builder() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/trades",
+            &[("limit", "200")],
+            HttpResponse::new(200, YUSDC_TRADES),
+        );
+        let s = mock_server(&mock);
 
         let mut tcb = TradeCallBuilder::new(&s);
 
         let records = tcb.limit(200).call().unwrap();
 
-        assert_eq!(records._embedded.records.len(), 200);
+        assert_eq!(records._embedded.records.len(), 2);
     }
 
     #[test]
     fn test_trade_call_builder_asset_pair() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/trades",
+            &[
+                ("base_asset_type", "native"),
+                ("counter_asset_type", "credit_alphanum12"),
+                ("counter_asset_code", "yUSDC"),
+                (
+                    "counter_asset_issuer",
+                    "GDGTVWSM4MGS4T7Z6W4RPWOCHE2I6RDFCIFZGS3DOA63LWQTRNZNTTFF",
+                ),
+                ("limit", "2"),
+            ],
+            HttpResponse::new(200, YUSDC_TRADES),
+        );
+        let s = mock_server(&mock);
 
         let native = Asset::native();
         let y_usdc = Asset::new(
@@ -131,8 +238,13 @@ mod tests {
 
     #[test]
     fn test_for_type() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/trades",
+            &[("trade_type", "orderbook")],
+            HttpResponse::new(200, YUSDC_TRADES),
+        );
+        let s = mock_server(&mock);
 
         let _records = s.trades().for_type(TradeType::Orderbook).call().unwrap();
     }
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
 use crate::api_call::api_call;
-use crate::endpoints::{horizon::Record, CallBuilder, Server};
+#[cfg(feature = "async")]
+use crate::api_call::api_call_async;
+use crate::endpoints::{horizon::Record, CallBuilder, EventStream, Pages, Records, Server};
 use crate::types::Transaction;
 use crate::utils::{Direction, Endpoint};
+use crate::Error;
 
 #[derive(Debug)]
 pub struct TransactionCallBuilder<'a> {
-    server_url: &'a str,
+    server: &'a Server,
     endpoint: Endpoint,
     query_params: HashMap<String, String>,
-    token: &'a Option<String>,
 }
 
 impl<'a> TransactionCallBuilder<'a> {
     pub fn new(s: &'a Server) -> Self {
         Self {
-            server_url: &s.server_url,
+            server: s,
             endpoint: Endpoint::None,
             query_params: HashMap::new(),
-            token: &s.options.auth_token,
         }
     }
 
@@ -29,6 +30,38 @@ impl<'a> TransactionCallBuilder<'a> {
 
         self
     }
+
+    pub fn stream(&self) -> EventStream<'a, Transaction> {
+        EventStream::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn call_async(&self) -> Result<Record<Transaction>, Error> {
+        api_call_async::<Record<Transaction>>(
+            self.url(),
+            crate::types::HttpMethod::GET,
+            &self.query_params,
+            self.server,
+        )
+        .await
+    }
+
+    pub fn pages(&self) -> Pages<'a, Transaction> {
+        Pages::new(self.url(), self.query_params.clone(), self.server)
+    }
+
+    pub fn records(&self) -> Records<'a, Transaction> {
+        self.pages().records()
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}{}",
+            &self.server.server_url,
+            self.endpoint.as_str(),
+            "/transactions",
+        )
+    }
 }
 
 impl<'a> CallBuilder<Transaction> for TransactionCallBuilder<'a> {
@@ -59,31 +92,78 @@ impl<'a> CallBuilder<Transaction> for TransactionCallBuilder<'a> {
         self
     }
 
-    fn call(&self) -> Result<Record<Transaction>, anyhow::Error> {
-        let url = format!(
-            "{}{}{}",
-            &self.server_url,
-            self.endpoint.as_str(),
-            "/transactions",
-        );
+    fn call(&self) -> Result<Record<Transaction>, Error> {
+        let url = self.url();
 
         api_call::<Record<Transaction>>(
             url,
             crate::types::HttpMethod::GET,
             &self.query_params,
-            self.token,
+            self.server,
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::transport::{mock_server, HttpResponse, MockTransport};
 
     #[test]
     fn limit_transaction_call_builder() {
-        let s = Server::new(String::from("https://horizon.stellar.org"), None)
-            .expect("Cannot connect to insecure horizon server");
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/transactions",
+            &[("limit", "200")],
+            HttpResponse::new(
+                200,
+                r#"{
+                    "_links": {
+                        "self": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/transactions?cursor=&limit=200&order=asc"},
+                        "next": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/transactions?cursor=178454385453166592&limit=200&order=asc"},
+                        "prev": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM/transactions?cursor=178454385453166592&limit=200&order=desc"}
+                    },
+                    "_embedded": {"records": [
+                        {
+                            "memo": "stellar_sdk_test",
+                            "memo_bytes": "c3RlbGxhcl9zZGtfdGVzdA==",
+                            "_links": {
+                                "self": {"href": "https://horizon.stellar.org/transactions/7f6c6b3a0b5a3e1a1b4c2f6a7d3e9f0c1b2a3d45f0c2a4dcbc6b7d8f9bb2ebfa"},
+                                "account": {"href": "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM"},
+                                "ledger": {"href": "https://horizon.stellar.org/ledgers/41550321"},
+                                "operations": {"href": "https://horizon.stellar.org/transactions/7f6c6b3a0b5a3e1a1b4c2f6a7d3e9f0c1b2a3d45f0c2a4dcbc6b7d8f9bb2ebfa/operations{?cursor,limit,order}", "templated": true},
+                                "effects": {"href": "https://horizon.stellar.org/transactions/7f6c6b3a0b5a3e1a1b4c2f6a7d3e9f0c1b2a3d45f0c2a4dcbc6b7d8f9bb2ebfa/effects{?cursor,limit,order}", "templated": true},
+                                "precedes": {"href": "https://horizon.stellar.org/transactions?order=asc&cursor=178454385453166592"},
+                                "succeeds": {"href": "https://horizon.stellar.org/transactions?order=desc&cursor=178454385453166592"},
+                                "transaction": {"href": "https://horizon.stellar.org/transactions/7f6c6b3a0b5a3e1a1b4c2f6a7d3e9f0c1b2a3d45f0c2a4dcbc6b7d8f9bb2ebfa"}
+                            },
+                            "id": "7f6c6b3a0b5a3e1a1b4c2f6a7d3e9f0c1b2a3d45f0c2a4dcbc6b7d8f9bb2ebfa",
+                            "paging_token": "178454385453166592",
+                            "successful": true,
+                            "hash": "7f6c6b3a0b5a3e1a1b4c2f6a7d3e9f0c1b2a3d45f0c2a4dcbc6b7d8f9bb2ebfa",
+                            "ledger": 41550321,
+                            "created_at": "2022-06-23T10:21:07Z",
+                            "source_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                            "source_account_sequence": "129664371176506170",
+                            "fee_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                            "fee_charged": "100",
+                            "max_fee": "100",
+                            "operation_count": 1,
+                            "envelope_xdr": "",
+                            "result_xdr": "AAAAAAAAAGQAAAAAAAAAAQAAAAAAAAABAAAAAAAAAAA=",
+                            "fee_meta_xdr": "",
+                            "memo_type": "text",
+                            "signatures": [],
+                            "valid_after": "1970-01-01T00:00:00Z",
+                            "valid_before": "2022-06-23T10:26:03Z"
+                        }
+                    ]}
+                }"#,
+            ),
+        );
+        let s = mock_server(&mock);
 
         let mut tcb = TransactionCallBuilder::new(&s);
 
@@ -95,6 +175,6 @@ mod tests {
             .call()
             .unwrap();
 
-        assert_eq!(tx_records._embedded.records.len(), 200);
+        assert_eq!(tx_records._embedded.records.len(), 1);
     }
 }
@@ -0,0 +1,236 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::time::Duration;
+
+use crate::transport::HttpResponse;
+use crate::types::{ErrorResultCode, HorizonError};
+
+/// Every error returned by the SDK.
+///
+/// Horizon problem documents are kept intact, so callers can match on the
+/// variant instead of downcasting or parsing messages.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Horizon answered with a problem document, e.g. a 400 for a failed transaction
+    /// or a 5xx with details about the failure.
+    Horizon(Box<HorizonError>),
+    /// The requested resource does not exist. The problem document is missing when
+    /// the 404 came from a proxy or CDN in front of Horizon.
+    NotFound(Option<Box<HorizonError>>),
+    /// Horizon answered 429, the client went over its rate limit. `reset` is how
+    /// long until the limit resets, when Horizon said so.
+    RateLimited {
+        problem: Option<Box<HorizonError>>,
+        reset: Option<Duration>,
+    },
+    /// A non 2xx response whose body is not a problem document.
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    /// The request never got a response, e.g. DNS, TLS or connection failures.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    /// A cassette in replay mode holds no response for the request.
+    UnmatchedRequest(String),
+    /// The request timed out.
+    Timeout,
+    /// The response body could not be decoded.
+    Decode(serde_json::Error),
+    /// The stellar.toml file could not be decoded.
+    Toml(toml::de::Error),
+    /// An XDR value could not be encoded or decoded.
+    Xdr(stellar_base::error::Error),
+    InvalidStrKey(&'static str),
+    InvalidAsset(&'static str),
+    InvalidKeypair(&'static str),
+    InvalidAmount(String),
+    InvalidPrice(String),
+    InvalidUrl(String),
+    /// The server URL is neither https nor, with `allow_http`, http.
+    UnsupportedScheme(String),
+    InvalidTransaction(&'static str),
+    /// A root certificate given in `ServerOptions` could not be loaded.
+    InvalidCertificate(String),
+    /// The destination account requires a memo (SEP-29) and the transaction has none.
+    AccountRequiresMemo(String),
+}
+
+impl Error {
+    /// Builds the error for a non 2xx response, keeping the problem document when
+    /// Horizon sent one.
+    pub(crate) fn from_response(url: &str, res: HttpResponse) -> Self {
+        let problem = serde_json::from_str::<HorizonError>(&res.body)
+            .ok()
+            .map(Box::new);
+
+        match (res.status, problem) {
+            (429, problem) => Error::RateLimited {
+                problem,
+                reset: res
+                    .header("X-RateLimit-Reset")
+                    .and_then(|reset| reset.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs),
+            },
+            (404, problem) => Error::NotFound(problem),
+            (_, Some(problem)) => Error::Horizon(problem),
+            (status, None) => Error::Status {
+                url: String::from(url),
+                status,
+                body: res.body,
+            },
+        }
+    }
+
+    /// The HTTP status code of the response, if there was one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Horizon(problem) => Some(problem.status as u16),
+            Error::NotFound(_) => Some(404),
+            Error::RateLimited { .. } => Some(429),
+            Error::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The result codes of a failed transaction submission, if Horizon sent them.
+    pub fn result_codes(&self) -> Option<&ErrorResultCode> {
+        self.horizon_error()?.extras.as_ref()?.result_codes.as_ref()
+    }
+
+    /// The problem document Horizon sent back, if there was one.
+    pub fn horizon_error(&self) -> Option<&HorizonError> {
+        match self {
+            Error::Horizon(problem) => Some(problem.as_ref()),
+            Error::NotFound(problem) | Error::RateLimited { problem, .. } => problem.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Horizon(problem) => write!(f, "horizon error: {}", problem.title),
+            Error::NotFound(Some(problem)) => write!(f, "not found: {}", problem.title),
+            Error::NotFound(None) => write!(f, "not found"),
+            Error::RateLimited {
+                reset: Some(reset), ..
+            } => write!(f, "rate limited, resets in {}s", reset.as_secs()),
+            Error::RateLimited { .. } => write!(f, "rate limited"),
+            Error::Status { url, status, .. } => write!(f, "{}: status code {}", url, status),
+            Error::Transport(e) => write!(f, "transport error: {}", e),
+            Error::UnmatchedRequest(request) => write!(f, "unmatched request: {}", request),
+            Error::Timeout => write!(f, "request timed out"),
+            Error::Decode(e) => write!(f, "cannot decode response: {}", e),
+            Error::Toml(e) => write!(f, "cannot decode stellar.toml: {}", e),
+            Error::Xdr(e) => write!(f, "xdr error: {}", e),
+            Error::InvalidStrKey(reason) => write!(f, "invalid str key: {}", reason),
+            Error::InvalidAsset(reason) => write!(f, "invalid asset: {}", reason),
+            Error::InvalidKeypair(reason) => write!(f, "invalid keypair: {}", reason),
+            Error::InvalidAmount(amount) => write!(f, "invalid amount: {}", amount),
+            Error::InvalidPrice(price) => write!(f, "invalid price: {}", price),
+            Error::InvalidUrl(reason) => write!(f, "invalid url: {}", reason),
+            Error::UnsupportedScheme(scheme) => write!(f, "unsupported url scheme: {}", scheme),
+            Error::InvalidTransaction(reason) => write!(f, "invalid transaction: {}", reason),
+            Error::InvalidCertificate(reason) => write!(f, "invalid certificate: {}", reason),
+            Error::AccountRequiresMemo(account_id) => {
+                write!(f, "account {} requires a memo", account_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Horizon(problem)
+            | Error::NotFound(Some(problem))
+            | Error::RateLimited {
+                problem: Some(problem),
+                ..
+            } => Some(problem.as_ref()),
+            Error::Transport(e) => Some(e.as_ref()),
+            Error::Decode(e) => Some(e),
+            Error::Toml(e) => Some(e),
+            Error::Xdr(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => Error::Timeout,
+            _ => Error::Transport(Box::new(e)),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Decode(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Error::Toml(e)
+    }
+}
+
+impl From<stellar_base::error::Error> for Error {
+    fn from(e: stellar_base::error::Error) -> Self {
+        Error::Xdr(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response() {
+        let problem = r#"{"type": "https://stellar.org/horizon-errors/not_found", "title": "Resource Missing", "status": 404}"#;
+
+        let err = Error::from_response(
+            "https://horizon.stellar.org/ledgers/1",
+            HttpResponse::new(404, problem),
+        );
+        assert!(matches!(err, Error::NotFound(Some(_))));
+        assert_eq!(Some(404), err.status());
+
+        let err = Error::from_response(
+            "https://horizon.stellar.org/ledgers/1",
+            HttpResponse::new(404, "<html>Not Found</html>"),
+        );
+        assert!(matches!(err, Error::NotFound(None)));
+        assert_eq!(Some(404), err.status());
+        assert!(err.horizon_error().is_none());
+
+        let problem = r#"{"type": "https://stellar.org/horizon-errors/server_error", "title": "Internal Server Error", "status": 500}"#;
+
+        let err = Error::from_response(
+            "https://horizon.stellar.org/ledgers/1",
+            HttpResponse::new(500, problem),
+        );
+        assert_eq!("Internal Server Error", err.horizon_error().unwrap().title);
+
+        let err = Error::from_response(
+            "https://horizon.stellar.org/ledgers/1",
+            HttpResponse::new(502, "Bad Gateway"),
+        );
+        assert!(matches!(err, Error::Status { status: 502, .. }));
+
+        let err = Error::from_response(
+            "https://horizon.stellar.org/ledgers/1",
+            HttpResponse::new(429, "Too Many Requests").with_header("X-RateLimit-Reset", "42"),
+        );
+        assert!(matches!(
+            err,
+            Error::RateLimited { reset: Some(reset), .. } if reset == Duration::from_secs(42)
+        ));
+    }
+}
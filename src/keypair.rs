@@ -1,9 +1,11 @@
-use anyhow::bail;
 use nacl::sign::{generate_keypair, signature, verify};
 use stellar_base::crypto::{SecretKey, SodiumKeyPair};
+use stellar_base::transaction::{FeeBumpTransaction, Transaction};
+use stellar_base::Network;
 use str_key::StrKey;
 
 use crate::str_key;
+use crate::Error;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Keypair {
@@ -14,9 +16,9 @@ pub struct Keypair {
 
 #[allow(dead_code)]
 impl Keypair {
-    fn new_from_secret_key(secret_seed: Vec<u8>) -> Result<Self, anyhow::Error> {
+    fn new_from_secret_key(secret_seed: Vec<u8>) -> Result<Self, Error> {
         if secret_seed.len() != 32 {
-            bail!("secret_key length is invalid")
+            return Err(Error::InvalidKeypair("secret_key length is invalid"));
         }
 
         let mut cloned_secret_key = secret_seed.clone();
@@ -34,9 +36,9 @@ impl Keypair {
         })
     }
 
-    fn new_from_public_key(public_key: Vec<u8>) -> Result<Self, anyhow::Error> {
+    fn new_from_public_key(public_key: Vec<u8>) -> Result<Self, Error> {
         if public_key.len() != 32 {
-            bail!("public_key length is invalid")
+            return Err(Error::InvalidKeypair("public_key length is invalid"));
         }
 
         Ok(Self {
@@ -46,18 +48,18 @@ impl Keypair {
         })
     }
 
-    pub fn from_secret_key(secret: &str) -> Result<Self, anyhow::Error> {
+    pub fn from_secret_key(secret: &str) -> Result<Self, Error> {
         let raw_secret = StrKey::decode_ed25519_secret_seed(secret)?;
 
         Keypair::from_raw_ed25519_seed(&raw_secret)
     }
 
-    pub fn from_public_key(public_key: &str) -> Result<Self, anyhow::Error> {
+    pub fn from_public_key(public_key: &str) -> Result<Self, Error> {
         let decoded = StrKey::decode_ed25519_public_key(public_key)?;
         // let decoded = decode_check(&VersionBytes::Ed25519PublicKey, public_key);
 
         if decoded.len() != 32 {
-            bail!("Invalid Stellar public key")
+            return Err(Error::InvalidStrKey("Invalid Stellar public key"));
         }
 
         Ok(Self {
@@ -67,7 +69,7 @@ impl Keypair {
         })
     }
 
-    pub fn from_raw_ed25519_seed(seed: &[u8]) -> Result<Self, anyhow::Error> {
+    pub fn from_raw_ed25519_seed(seed: &[u8]) -> Result<Self, Error> {
         Self::new_from_secret_key(seed.to_vec())
     }
 
@@ -79,9 +81,9 @@ impl Keypair {
         &self.public_key
     }
 
-    pub fn secret_key(&mut self) -> Result<String, anyhow::Error> {
+    pub fn secret_key(&mut self) -> Result<String, Error> {
         match &mut self.secret_seed {
-            None => bail!("no secret_key available"),
+            None => Err(Error::InvalidKeypair("no secret_key available")),
             Some(s) => Ok(StrKey::encode_ed25519_secret_seed(s)),
         }
     }
@@ -94,26 +96,61 @@ impl Keypair {
         self.secret_key.is_some()
     }
 
-    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
         if !self.can_sign() {
-            bail!("cannot sign, no secret_key available")
+            return Err(Error::InvalidKeypair(
+                "cannot sign, no secret_key available",
+            ));
         }
 
         if let Some(s) = &self.secret_key {
             match signature(data, s) {
-                Err(_) => bail!("error while signing"),
+                Err(_) => return Err(Error::InvalidKeypair("error while signing")),
                 Ok(m) => return Ok(m),
             }
         }
 
-        bail!("error while signing")
+        Err(Error::InvalidKeypair("error while signing"))
+    }
+
+    /// Adds a signature for `transaction` on `network`.
+    pub fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        network: &Network,
+    ) -> Result<(), Error> {
+        let keypair = self.sodium_keypair()?;
+
+        Ok(transaction.sign(keypair.as_ref(), network)?)
+    }
+
+    /// Adds a signature for `transaction` on `network`, usually by its fee source.
+    pub fn sign_fee_bump(
+        &self,
+        transaction: &mut FeeBumpTransaction,
+        network: &Network,
+    ) -> Result<(), Error> {
+        let keypair = self.sodium_keypair()?;
+
+        Ok(transaction.sign(keypair.as_ref(), network)?)
+    }
+
+    fn sodium_keypair(&self) -> Result<SodiumKeyPair, Error> {
+        match &self.secret_seed {
+            None => Err(Error::InvalidKeypair(
+                "cannot sign, no secret_key available",
+            )),
+            Some(s) => Ok(SodiumKeyPair::from_secret_seed(
+                &StrKey::encode_ed25519_secret_seed(s),
+            )?),
+        }
     }
 
     pub fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
         verify(signature, data, &self.public_key).is_ok()
     }
 
-    pub fn random() -> Result<Self, anyhow::Error> {
+    pub fn random() -> Result<Self, Error> {
         Self::new_from_secret_key(rand::random::<[u8; 32]>().to_vec())
     }
 }
@@ -125,19 +162,17 @@ impl From<Keypair> for SodiumKeyPair {
         let secret_key: String = sdk_keypair_inner
             .secret_key()
             .expect("Failed to get the secret key from the stellar_sdk::Keypair");
-        let stellar_base_keypair = SodiumKeyPair::from_secret_seed(&secret_key)
-            .expect("Failed to convert to generate stellar_base::KeyPair from the secret seed");
-        stellar_base_keypair
+        SodiumKeyPair::from_secret_seed(&secret_key)
+            .expect("Failed to convert to generate stellar_base::KeyPair from the secret seed")
     }
 }
 
 impl From<SodiumKeyPair> for Keypair {
     fn from(base_keypair: SodiumKeyPair) -> Self {
         let secret_key_struct: &SecretKey = base_keypair.secret_key();
-        let secret_key: String = secret_key_struct.secret_seed().into();
-        let stellar_sdk_keypair = Keypair::from_secret_key(&secret_key)
-            .expect("Failed to convert to generate stellar_sdk::Keypair from the secret seed");
-        stellar_sdk_keypair
+        let secret_key: String = secret_key_struct.secret_seed();
+        Keypair::from_secret_key(&secret_key)
+            .expect("Failed to convert to generate stellar_sdk::Keypair from the secret seed")
     }
 }
 
@@ -5,7 +5,7 @@
 //! ## Usage:
 //!
 //!
-//! ```
+//! ```no_run
 //! use stellar_sdk::{CallBuilder, Server, types::Asset, utils::{Direction, Endpoint}};
 //!
 //!     let s = String::from("https://horizon.stellar.org");
@@ -52,32 +52,81 @@
 
 mod api_call;
 mod endpoints;
+mod error;
 #[cfg(feature = "nacl")]
 mod keypair;
 mod operations;
 mod str_key;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
 pub mod types;
 pub mod utils;
 
+#[cfg(feature = "async")]
+pub use endpoints::AsyncServer;
 pub use endpoints::CallBuilder;
-pub use endpoints::Server;
+pub use endpoints::EventStream;
 pub use endpoints::StellarTomlResolver;
+pub use endpoints::{Pages, Records};
+pub use endpoints::{Server, ServerOptions, SubmitTransactionOptions, Timebounds};
+pub use error::Error;
 #[cfg(feature = "nacl")]
 pub use keypair::Keypair;
+pub use operations::{SetOptionsParams, TransactionBuilder};
 pub use str_key::StrKey;
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use crate::{
-        types::Asset,
+        transport::{mock_server, HttpResponse, MockTransport},
+        types::{account_json, Asset},
         utils::{Direction, Endpoint},
-        CallBuilder, Server,
+        CallBuilder,
     };
 
     #[test]
     fn test_app() {
-        let s = String::from("https://horizon.stellar.org");
-        let s = Server::new(s, None).expect("Cannot connect to insecure horizon server");
+        let empty_page = |url: &str| {
+            HttpResponse::new(
+                200,
+                &format!(
+                    r#"{{"_links": {{"self": {{"href": "{}"}}}}, "_embedded": {{"records": []}}}}"#,
+                    url
+                ),
+            )
+        };
+        let mock = Arc::new(MockTransport::new());
+        mock.add_json(
+            "https://horizon.stellar.org/accounts/GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+            &account_json(
+                "https://horizon.stellar.org",
+                "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                1,
+            )
+            .to_string(),
+        )
+        .add_response_for_query(
+            "https://horizon.stellar.org/accounts/GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV/transactions",
+            &[("order", "desc"), ("limit", "2"), ("include_failed", "false")],
+            empty_page("https://horizon.stellar.org/accounts/GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV/transactions?cursor=&include_failed=false&limit=2&order=desc"),
+        )
+        .add_response_for_query(
+            "https://horizon.stellar.org/trades",
+            &[("base_asset_code", "yXLM"), ("counter_asset_type", "native")],
+            empty_page("https://horizon.stellar.org/trades?base_asset_code=yXLM&base_asset_issuer=GARDNV3Q7YGT4AKSDF25LT32YSCCW4EV22Y2TV3I2PU2MMXJTEDL5T55&base_asset_type=credit_alphanum4&counter_asset_type=native&cursor=&limit=2&order=asc"),
+        )
+        .add_response_for_query(
+            "https://horizon.stellar.org/liquidity_pools",
+            &[(
+                "reserves",
+                "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+            )],
+            empty_page("https://horizon.stellar.org/liquidity_pools?cursor=&limit=10&order=asc&reserves=USDC%3AGA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"),
+        );
+        let s = mock_server(&mock);
 
         let _my_acc = s
             .load_account("GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM")
@@ -1 +1,3 @@
+mod transaction_builder;
 
+pub use transaction_builder::{SetOptionsParams, TransactionBuilder};
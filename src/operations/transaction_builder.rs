@@ -0,0 +1,895 @@
+use chrono::{TimeZone, Utc};
+use data_encoding::HEXLOWER_PERMISSIVE;
+use stellar_base::account::{AccountFlags, DataValue, TrustLineFlags};
+use stellar_base::amount::{Amount as BaseAmount, Price as BasePrice, Stroops};
+use stellar_base::asset::{CreditAssetType, TrustLineAsset};
+use stellar_base::claim::{ClaimPredicate, ClaimableBalanceId, Claimant};
+use stellar_base::crypto::{MuxedAccount, Signer, SignerKey};
+use stellar_base::ledger::LedgerKey;
+use stellar_base::liquidity_pool::LiquidityPoolId;
+use stellar_base::time_bounds::TimeBounds;
+use stellar_base::transaction::TransactionBuilder as BaseTransactionBuilder;
+use stellar_base::{xdr, Asset as BaseAsset, Memo, Operation, PublicKey, Transaction};
+
+#[cfg(feature = "nacl")]
+use stellar_base::Network;
+
+use crate::types::{Account, Amount, Asset, Price};
+#[cfg(feature = "nacl")]
+use crate::Keypair;
+use crate::{Error, Timebounds};
+
+/// The fee of a constant product liquidity pool, in basis points. The protocol allows no other
+/// value (`LIQUIDITY_POOL_FEE_V18` in the XDR).
+const LIQUIDITY_POOL_FEE: i32 = 30;
+
+/// The optional fields of a `set_options` operation. Anything left as `None` is
+/// not changed on the account.
+#[derive(Debug, Clone, Default)]
+pub struct SetOptionsParams {
+    pub inflation_destination: Option<String>,
+    pub clear_flags: Option<AccountFlags>,
+    pub set_flags: Option<AccountFlags>,
+    pub master_weight: Option<u32>,
+    pub low_threshold: Option<u32>,
+    pub medium_threshold: Option<u32>,
+    pub high_threshold: Option<u32>,
+    pub home_domain: Option<String>,
+    /// An ed25519 public key and its weight, a weight of 0 removes the signer.
+    pub signer: Option<(String, u32)>,
+}
+
+/// Builds a transaction for an account loaded from Horizon.
+///
+/// The transaction uses the next sequence number of the account, which is bumped
+/// once the transaction is built, so the same `Account` can be used for the next one.
+///
+/// ```no_run
+/// use stellar_sdk::{types::{Amount, Asset}, Server, TransactionBuilder};
+///
+/// let s = Server::new(String::from("https://horizon-testnet.stellar.org"), None).unwrap();
+/// let mut account = s
+///     .load_account("GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM")
+///     .unwrap();
+///
+/// let tx = TransactionBuilder::new(&mut account, 100)
+///     .payment(
+///         "GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN",
+///         &Asset::native(),
+///         "10".parse::<Amount>().unwrap(),
+///     )
+///     .memo_text("hello")
+///     .build()
+///     .unwrap();
+///
+/// let response = s.submit_transaction(tx);
+/// ```
+pub struct TransactionBuilder<'a> {
+    account: &'a mut Account,
+    base_fee: u32,
+    memo: Memo,
+    timebounds: Option<Timebounds>,
+    operations: Vec<Operation>,
+    // The first error hit while adding operations, returned by build
+    error: Option<Error>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    /// `base_fee` is the fee per operation, in stroops.
+    pub fn new(account: &'a mut Account, base_fee: u32) -> Self {
+        Self {
+            account,
+            base_fee,
+            memo: Memo::new_none(),
+            timebounds: None,
+            operations: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn timebounds(mut self, timebounds: Timebounds) -> Self {
+        self.timebounds = Some(timebounds);
+        self
+    }
+
+    pub fn memo_text(self, text: &str) -> Self {
+        let memo = Memo::new_text(text).map_err(Error::from);
+        self.set_memo(memo)
+    }
+
+    pub fn memo_id(self, id: u64) -> Self {
+        self.set_memo(Ok(Memo::new_id(id)))
+    }
+
+    pub fn memo_hash(self, hash: &[u8]) -> Self {
+        let memo = Memo::new_hash(hash).map_err(Error::from);
+        self.set_memo(memo)
+    }
+
+    pub fn memo_return(self, hash: &[u8]) -> Self {
+        let memo = Memo::new_return(hash).map_err(Error::from);
+        self.set_memo(memo)
+    }
+
+    /// Adds an operation built with `stellar_base` directly.
+    pub fn add_operation(self, operation: Operation) -> Self {
+        self.push(Ok(operation))
+    }
+
+    /// Sets the source account of the operation added last. Without it an operation
+    /// runs on behalf of the transaction source account.
+    pub fn operation_source(mut self, account_id: &str) -> Self {
+        let source = public_key(account_id).map(MuxedAccount::from);
+
+        match (source, self.operations.last_mut()) {
+            (Ok(source), Some(operation)) => *operation.source_account_mut() = Some(source),
+            (Err(e), _) => self.fail(e),
+            (_, None) => self.fail(Error::InvalidTransaction("no operation to set a source on")),
+        }
+
+        self
+    }
+
+    pub fn create_account(self, destination: &str, starting_balance: Amount) -> Self {
+        let operation = || {
+            Ok(Operation::new_create_account()
+                .with_destination(public_key(destination)?)
+                .with_starting_balance(Stroops::from(starting_balance))?
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn payment(self, destination: &str, asset: &Asset, amount: Amount) -> Self {
+        let operation = || {
+            Ok(Operation::new_payment()
+                .with_destination(public_key(destination)?)
+                .with_asset(BaseAsset::try_from(asset)?)
+                .with_amount(Stroops::from(amount))?
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Sends at most `send_max` of `send_asset` so that `destination` receives
+    /// exactly `dest_amount` of `dest_asset`, converting through `path`.
+    pub fn path_payment_strict_receive(
+        self,
+        destination: &str,
+        send_asset: &Asset,
+        send_max: Amount,
+        dest_asset: &Asset,
+        dest_amount: Amount,
+        path: &[Asset],
+    ) -> Self {
+        let operation = || {
+            let mut builder = Operation::new_path_payment_strict_receive()
+                .with_destination(public_key(destination)?)
+                .with_send_asset(BaseAsset::try_from(send_asset)?)
+                .with_send_max(Stroops::from(send_max))?
+                .with_destination_asset(BaseAsset::try_from(dest_asset)?)
+                .with_destination_amount(Stroops::from(dest_amount))?;
+            for asset in path {
+                builder = builder.add_asset(BaseAsset::try_from(asset)?);
+            }
+
+            Ok(builder.build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Sends exactly `send_amount` of `send_asset` so that `destination` receives
+    /// at least `dest_min` of `dest_asset`, converting through `path`.
+    pub fn path_payment_strict_send(
+        self,
+        destination: &str,
+        send_asset: &Asset,
+        send_amount: Amount,
+        dest_asset: &Asset,
+        dest_min: Amount,
+        path: &[Asset],
+    ) -> Self {
+        let operation = || {
+            let mut builder = Operation::new_path_payment_strict_send()
+                .with_destination(public_key(destination)?)
+                .with_send_asset(BaseAsset::try_from(send_asset)?)
+                .with_send_amount(Stroops::from(send_amount))?
+                .with_destination_asset(BaseAsset::try_from(dest_asset)?)
+                .with_destination_min(Stroops::from(dest_min))?;
+            for asset in path {
+                builder = builder.add_asset(BaseAsset::try_from(asset)?);
+            }
+
+            Ok(builder.build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Creates, updates or (with a zero `amount`) deletes an offer. An `offer_id` of
+    /// `None` creates a new offer.
+    pub fn manage_sell_offer(
+        self,
+        selling: &Asset,
+        buying: &Asset,
+        amount: Amount,
+        price: Price,
+        offer_id: Option<i64>,
+    ) -> Self {
+        let operation = || {
+            Ok(Operation::new_manage_sell_offer()
+                .with_selling_asset(BaseAsset::try_from(selling)?)
+                .with_buying_asset(BaseAsset::try_from(buying)?)
+                .with_amount(BaseAmount::try_from(amount)?)?
+                .with_price(BasePrice::try_from(price)?)
+                .with_offer_id(offer_id)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Like `manage_sell_offer`, with `buy_amount` being the amount of `buying` to get.
+    pub fn manage_buy_offer(
+        self,
+        selling: &Asset,
+        buying: &Asset,
+        buy_amount: Amount,
+        price: Price,
+        offer_id: Option<i64>,
+    ) -> Self {
+        let operation = || {
+            Ok(Operation::new_manage_buy_offer()
+                .with_selling_asset(BaseAsset::try_from(selling)?)
+                .with_buying_asset(BaseAsset::try_from(buying)?)
+                .with_buy_amount(BaseAmount::try_from(buy_amount)?)?
+                .with_price(BasePrice::try_from(price)?)
+                .with_offer_id(offer_id)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn create_passive_sell_offer(
+        self,
+        selling: &Asset,
+        buying: &Asset,
+        amount: Amount,
+        price: Price,
+    ) -> Self {
+        let operation = || {
+            Ok(Operation::new_create_passive_sell_offer()
+                .with_selling_asset(BaseAsset::try_from(selling)?)
+                .with_buying_asset(BaseAsset::try_from(buying)?)
+                .with_amount(BaseAmount::try_from(amount)?)?
+                .with_price(BasePrice::try_from(price)?)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn set_options(self, options: SetOptionsParams) -> Self {
+        let operation = || {
+            let inflation_destination = match options.inflation_destination {
+                Some(account_id) => Some(public_key(&account_id)?),
+                None => None,
+            };
+            let signer = match options.signer {
+                Some((key, weight)) => Some(Signer::new(
+                    SignerKey::new_from_public_key(public_key(&key)?),
+                    weight,
+                )),
+                None => None,
+            };
+
+            let mut operation = Operation::new_set_options()
+                .with_inflation_destination(inflation_destination)
+                .with_clear_flags(options.clear_flags)
+                .with_set_flags(options.set_flags)
+                .with_master_weight(options.master_weight)
+                .with_low_threshold(options.low_threshold)
+                .with_medium_threshold(options.medium_threshold)
+                .with_high_threshold(options.high_threshold)
+                .with_signer(signer)
+                .build()?;
+            if let Operation::SetOptions(ref mut set_options) = operation {
+                *set_options.home_domain_mut() = options.home_domain;
+            }
+
+            Ok(operation)
+        };
+
+        self.push(operation())
+    }
+
+    /// Adds, updates or (with a zero `limit`) removes a trustline. A `limit` of `None`
+    /// trusts the asset up to the maximum amount.
+    pub fn change_trust(self, asset: &Asset, limit: Option<Amount>) -> Self {
+        let operation = || {
+            let asset = match BaseAsset::try_from(asset)?.to_xdr()? {
+                xdr::Asset::AssetTypeNative(()) => xdr::ChangeTrustAsset::AssetTypeNative(()),
+                xdr::Asset::AssetTypeCreditAlphanum4(credit) => {
+                    xdr::ChangeTrustAsset::AssetTypeCreditAlphanum4(credit)
+                }
+                xdr::Asset::AssetTypeCreditAlphanum12(credit) => {
+                    xdr::ChangeTrustAsset::AssetTypeCreditAlphanum12(credit)
+                }
+            };
+
+            change_trust_operation(asset, limit)
+        };
+
+        self.push(operation())
+    }
+
+    /// Like `change_trust`, for the shares of the liquidity pool of `asset_a` and
+    /// `asset_b`. The assets have to be in lexicographic order, as Horizon lists them.
+    pub fn change_trust_pool_share(
+        self,
+        asset_a: &Asset,
+        asset_b: &Asset,
+        limit: Option<Amount>,
+    ) -> Self {
+        let operation = || {
+            let parameters = xdr::LiquidityPoolParameters::LiquidityPoolConstantProduct(
+                xdr::LiquidityPoolConstantProductParameters {
+                    asset_a: BaseAsset::try_from(asset_a)?.to_xdr()?,
+                    asset_b: BaseAsset::try_from(asset_b)?.to_xdr()?,
+                    fee: xdr::Int32::new(LIQUIDITY_POOL_FEE),
+                },
+            );
+
+            change_trust_operation(xdr::ChangeTrustAsset::AssetTypePoolShare(parameters), limit)
+        };
+
+        self.push(operation())
+    }
+
+    /// Deprecated by the network in favour of `set_trustline_flags`, kept for issuers
+    /// that still use it.
+    pub fn allow_trust(self, trustor: &str, asset_code: &str, authorize: TrustLineFlags) -> Self {
+        let operation = || {
+            let asset = if asset_code.len() <= 4 {
+                CreditAssetType::CreditAlphaNum4(String::from(asset_code))
+            } else {
+                CreditAssetType::CreditAlphaNum12(String::from(asset_code))
+            };
+
+            Ok(Operation::new_allow_trust()
+                .with_trustor(public_key(trustor)?)
+                .with_asset(asset)
+                .with_authorize_flags(authorize)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Merges the source account into `destination`, removing it from the ledger.
+    pub fn account_merge(self, destination: &str) -> Self {
+        let operation = || {
+            Ok(Operation::new_account_merge()
+                .with_destination(MuxedAccount::from(public_key(destination)?))
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn inflation(self) -> Self {
+        self.push(Ok(Operation::new_inflation().build()))
+    }
+
+    /// Sets, or with a `value` of `None` removes, a data entry of the account.
+    pub fn manage_data(self, name: &str, value: Option<&[u8]>) -> Self {
+        let operation = || {
+            let value = match value {
+                Some(value) => Some(DataValue::from_slice(value)?),
+                None => None,
+            };
+
+            Ok(Operation::new_manage_data()
+                .with_data_name(String::from(name))
+                .with_data_value(value)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn bump_sequence(self, bump_to: i64) -> Self {
+        let operation = || {
+            Ok(Operation::new_bump_sequence()
+                .with_bump_to(bump_to)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Each claimant is an account id together with the predicate it has to meet to
+    /// claim the balance.
+    pub fn create_claimable_balance(
+        self,
+        asset: &Asset,
+        amount: Amount,
+        claimants: Vec<(String, ClaimPredicate)>,
+    ) -> Self {
+        let operation = || {
+            let mut builder = Operation::new_create_claimable_balance()
+                .with_asset(BaseAsset::try_from(asset)?)
+                .with_amount(Stroops::from(amount))?;
+            for (destination, predicate) in claimants {
+                builder = builder.add_claimant(Claimant::new(public_key(&destination)?, predicate));
+            }
+
+            Ok(builder.build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// `balance_id` as returned by Horizon, e.g. `00000000da0d57da...`.
+    pub fn claim_claimable_balance(self, balance_id: &str) -> Self {
+        let operation = || {
+            Ok(Operation::new_claim_claimable_balance()
+                .with_claimable_balance_id(claimable_balance_id(balance_id)?)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Pays the reserves of everything `sponsored_id` creates until a matching
+    /// `end_sponsoring_future_reserves`.
+    pub fn begin_sponsoring_future_reserves(self, sponsored_id: &str) -> Self {
+        let operation = || {
+            Ok(Operation::new_begin_sponsoring_future_reserves()
+                .with_sponsored_id(public_key(sponsored_id)?)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Has to be signed by, and is added with the source account of, the sponsored account.
+    pub fn end_sponsoring_future_reserves(self, sponsored_id: &str) -> Self {
+        self.push(Ok(Operation::new_end_sponsoring_future_reserves().build()))
+            .operation_source(sponsored_id)
+    }
+
+    pub fn revoke_account_sponsorship(self, account_id: &str) -> Self {
+        let ledger_key = public_key(account_id).map(LedgerKey::Account);
+
+        self.revoke_sponsorship(ledger_key)
+    }
+
+    pub fn revoke_trustline_sponsorship(self, account_id: &str, asset: &Asset) -> Self {
+        let ledger_key = || {
+            let asset = match BaseAsset::try_from(asset)? {
+                BaseAsset::Native => TrustLineAsset::Native,
+                BaseAsset::Credit(credit) => TrustLineAsset::Credit(credit),
+            };
+
+            Ok(LedgerKey::Trustline(public_key(account_id)?, asset))
+        };
+
+        self.revoke_sponsorship(ledger_key())
+    }
+
+    pub fn revoke_offer_sponsorship(self, seller_id: &str, offer_id: i64) -> Self {
+        let ledger_key = public_key(seller_id).map(|seller| LedgerKey::Offer(seller, offer_id));
+
+        self.revoke_sponsorship(ledger_key)
+    }
+
+    pub fn revoke_data_sponsorship(self, account_id: &str, name: &str) -> Self {
+        let ledger_key =
+            public_key(account_id).map(|account| LedgerKey::Data(account, String::from(name)));
+
+        self.revoke_sponsorship(ledger_key)
+    }
+
+    pub fn revoke_claimable_balance_sponsorship(self, balance_id: &str) -> Self {
+        let ledger_key = claimable_balance_id(balance_id).map(LedgerKey::ClaimableBalance);
+
+        self.revoke_sponsorship(ledger_key)
+    }
+
+    pub fn revoke_liquidity_pool_sponsorship(self, liquidity_pool_id: &str) -> Self {
+        let ledger_key = self::liquidity_pool_id(liquidity_pool_id).map(LedgerKey::LiquidityPool);
+
+        self.revoke_sponsorship(ledger_key)
+    }
+
+    pub fn revoke_signer_sponsorship(self, account_id: &str, signer_key: &str) -> Self {
+        let operation = || {
+            Ok(Operation::new_revoke_sponsorship()
+                .with_signer(
+                    public_key(account_id)?,
+                    SignerKey::new_from_public_key(public_key(signer_key)?),
+                )
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn clawback(self, asset: &Asset, from: &str, amount: Amount) -> Self {
+        let operation = || {
+            Ok(Operation::new_clawback()
+                .with_asset(BaseAsset::try_from(asset)?)
+                .with_from(public_key(from)?)
+                .with_amount(Stroops::from(amount))?
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn clawback_claimable_balance(self, balance_id: &str) -> Self {
+        let operation = || {
+            Ok(Operation::new_clawback_claimable_balance()
+                .with_balance_id(claimable_balance_id(balance_id)?)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn set_trustline_flags(
+        self,
+        trustor: &str,
+        asset: &Asset,
+        clear_flags: TrustLineFlags,
+        set_flags: TrustLineFlags,
+    ) -> Self {
+        let operation = || {
+            Ok(Operation::new_set_trustline_flags()
+                .with_trustor(public_key(trustor)?)
+                .with_asset(BaseAsset::try_from(asset)?)
+                .with_clear_flags(clear_flags)
+                .with_set_flags(set_flags)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn liquidity_pool_deposit(
+        self,
+        liquidity_pool_id: &str,
+        max_amount_a: Amount,
+        max_amount_b: Amount,
+        min_price: Price,
+        max_price: Price,
+    ) -> Self {
+        let operation = || {
+            Ok(Operation::new_liquidity_pool_deposit()
+                .with_liquidity_pool_id(self::liquidity_pool_id(liquidity_pool_id)?)
+                .with_max_amount_a(Stroops::from(max_amount_a))?
+                .with_max_amount_b(Stroops::from(max_amount_b))?
+                .with_min_price(BasePrice::try_from(min_price)?)
+                .with_max_price(BasePrice::try_from(max_price)?)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    pub fn liquidity_pool_withdraw(
+        self,
+        liquidity_pool_id: &str,
+        amount: Amount,
+        min_amount_a: Amount,
+        min_amount_b: Amount,
+    ) -> Self {
+        let operation = || {
+            Ok(Operation::new_liquidity_pool_withdraw()
+                .with_liquidity_pool_id(self::liquidity_pool_id(liquidity_pool_id)?)
+                .with_amount(Stroops::from(amount))?
+                .with_min_amount_a(Stroops::from(min_amount_a))?
+                .with_min_amount_b(Stroops::from(min_amount_b))?
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+
+    /// Builds the transaction, ready for `Server::submit_transaction` once signed,
+    /// and increments the sequence number of the account.
+    pub fn build(self) -> Result<Transaction, Error> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        let sequence = self
+            .account
+            .sequence
+            .parse::<i64>()
+            .ok()
+            .and_then(|sequence| sequence.checked_add(1))
+            .ok_or(Error::InvalidTransaction("invalid account sequence"))?;
+        let source = public_key(&self.account.account_id)?;
+
+        let mut builder =
+            BaseTransactionBuilder::new(source, sequence, Stroops::new(self.base_fee.into()))
+                .with_memo(self.memo);
+        if let Some(timebounds) = self.timebounds {
+            builder = builder.with_time_bounds(time_bounds(&timebounds)?);
+        }
+        for operation in self.operations {
+            builder = builder.add_operation(operation);
+        }
+
+        let transaction = builder.into_transaction()?;
+        self.account.increment_sequence_number();
+
+        Ok(transaction)
+    }
+
+    /// Builds the transaction and signs it with every keypair in `signers`.
+    #[cfg(feature = "nacl")]
+    pub fn build_and_sign(
+        self,
+        signers: &[&Keypair],
+        network: &Network,
+    ) -> Result<Transaction, Error> {
+        let mut transaction = self.build()?;
+
+        for signer in signers {
+            signer.sign_transaction(&mut transaction, network)?;
+        }
+
+        Ok(transaction)
+    }
+
+    fn set_memo(mut self, memo: Result<Memo, Error>) -> Self {
+        match memo {
+            Ok(memo) => self.memo = memo,
+            Err(e) => self.fail(e),
+        }
+
+        self
+    }
+
+    fn push(mut self, operation: Result<Operation, Error>) -> Self {
+        match operation {
+            Ok(operation) => self.operations.push(operation),
+            Err(e) => self.fail(e),
+        }
+
+        self
+    }
+
+    fn fail(&mut self, e: Error) {
+        if self.error.is_none() {
+            self.error = Some(e);
+        }
+    }
+
+    fn revoke_sponsorship(self, ledger_key: Result<LedgerKey, Error>) -> Self {
+        let operation = || {
+            Ok(Operation::new_revoke_sponsorship()
+                .with_ledger_key(ledger_key?)
+                .build()?)
+        };
+
+        self.push(operation())
+    }
+}
+
+fn public_key(account_id: &str) -> Result<PublicKey, Error> {
+    PublicKey::from_account_id(account_id).map_err(|_| Error::InvalidStrKey("invalid account id"))
+}
+
+// stellar_base does not export its change trust asset, so the operation is built
+// from its xdr instead
+fn change_trust_operation(
+    asset: xdr::ChangeTrustAsset,
+    limit: Option<Amount>,
+) -> Result<Operation, Error> {
+    let limit = limit.unwrap_or(Amount::MAX);
+    if limit.is_negative() {
+        return Err(Error::InvalidAmount(limit.to_string()));
+    }
+
+    let operation = xdr::Operation {
+        source_account: None,
+        body: xdr::OperationBody::ChangeTrust(xdr::ChangeTrustOp {
+            line: asset,
+            limit: xdr::Int64::new(limit.to_stroops()),
+        }),
+    };
+
+    Ok(Operation::from_xdr(&operation)?)
+}
+
+// Horizon prefixes the 32 byte hash of a balance id with its 4 byte type
+fn claimable_balance_id(balance_id: &str) -> Result<ClaimableBalanceId, Error> {
+    let bytes = HEXLOWER_PERMISSIVE
+        .decode(balance_id.as_bytes())
+        .map_err(|_| Error::InvalidTransaction("invalid claimable balance id"))?;
+    let hash = match bytes.len() {
+        36 => bytes[4..].to_vec(),
+        _ => bytes,
+    };
+
+    Ok(ClaimableBalanceId::new(hash)?)
+}
+
+fn liquidity_pool_id(liquidity_pool_id: &str) -> Result<LiquidityPoolId, Error> {
+    let bytes = HEXLOWER_PERMISSIVE
+        .decode(liquidity_pool_id.as_bytes())
+        .map_err(|_| Error::InvalidTransaction("invalid liquidity pool id"))?;
+
+    Ok(LiquidityPoolId::new(bytes)?)
+}
+
+// A bound of 0 leaves that side of the time window open
+fn time_bounds(timebounds: &Timebounds) -> Result<TimeBounds, Error> {
+    let mut time_bounds = TimeBounds::always_valid();
+
+    if timebounds.min_time > 0 {
+        let lower = Utc
+            .timestamp_opt(timebounds.min_time, 0)
+            .single()
+            .ok_or(Error::InvalidTransaction("invalid min_time"))?;
+        time_bounds = time_bounds.with_lower(lower)?;
+    }
+    if timebounds.max_time > 0 {
+        let upper = Utc
+            .timestamp_opt(timebounds.max_time, 0)
+            .single()
+            .ok_or(Error::InvalidTransaction("invalid max_time"))?;
+        time_bounds = time_bounds.with_upper(upper)?;
+    }
+
+    Ok(time_bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_base::xdr::XDRSerialize;
+
+    use super::*;
+
+    const SOURCE: &str = "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM";
+    const DESTINATION: &str = "GAST24JSPH5S5Z2HC5PKEVQYDZIPFLOEC26KLVDNPVFVNNRALVTM6SCN";
+
+    fn account(sequence: i64) -> Account {
+        let json = crate::types::account_json("https://horizon.stellar.org", SOURCE, sequence);
+
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn usdc() -> Asset {
+        Asset::new(
+            String::from("USDC"),
+            String::from("GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_increments_sequence() {
+        let mut account = account(100);
+
+        let tx = TransactionBuilder::new(&mut account, 100)
+            .payment(DESTINATION, &Asset::native(), "1.5".parse().unwrap())
+            .change_trust(&usdc(), None)
+            .memo_text("stellar_sdk")
+            .timebounds(Timebounds {
+                min_time: 0,
+                max_time: 1651414699,
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(101, *tx.sequence());
+        assert_eq!(200, tx.fee().to_i64());
+        assert_eq!(2, tx.operations().len());
+        assert!(tx.memo().is_text());
+        assert_eq!("101", account.sequence);
+    }
+
+    #[test]
+    fn test_build_every_operation() {
+        let mut account = account(1);
+        let balance_id = "00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be";
+        let pool_id = "dd7b1ab831c273310ddbec6f97870aa83c2fbd78ce22aded37ecbf4f3380fac7";
+        let price = Price::new(1, 2).unwrap();
+        let one: Amount = "1".parse().unwrap();
+
+        let tx = TransactionBuilder::new(&mut account, 100)
+            .create_account(DESTINATION, one)
+            .path_payment_strict_receive(DESTINATION, &usdc(), one, &Asset::native(), one, &[])
+            .path_payment_strict_send(DESTINATION, &usdc(), one, &Asset::native(), one, &[])
+            .manage_sell_offer(&usdc(), &Asset::native(), one, price, None)
+            .manage_buy_offer(&usdc(), &Asset::native(), one, price, Some(7))
+            .create_passive_sell_offer(&usdc(), &Asset::native(), one, price)
+            .set_options(SetOptionsParams {
+                home_domain: Some(String::from("example.com")),
+                signer: Some((String::from(DESTINATION), 1)),
+                ..Default::default()
+            })
+            .change_trust_pool_share(&Asset::native(), &usdc(), None)
+            .allow_trust(DESTINATION, "USDC", TrustLineFlags::AUTHORIZED)
+            .manage_data("name", Some(b"value"))
+            .bump_sequence(10)
+            .create_claimable_balance(
+                &usdc(),
+                one,
+                vec![(
+                    String::from(DESTINATION),
+                    ClaimPredicate::new_unconditional(),
+                )],
+            )
+            .claim_claimable_balance(balance_id)
+            .begin_sponsoring_future_reserves(DESTINATION)
+            .end_sponsoring_future_reserves(DESTINATION)
+            .revoke_trustline_sponsorship(DESTINATION, &usdc())
+            .revoke_signer_sponsorship(SOURCE, DESTINATION)
+            .clawback(&usdc(), DESTINATION, one)
+            .clawback_claimable_balance(balance_id)
+            .set_trustline_flags(
+                DESTINATION,
+                &usdc(),
+                TrustLineFlags::empty(),
+                TrustLineFlags::AUTHORIZED,
+            )
+            .liquidity_pool_deposit(pool_id, one, one, price, price)
+            .liquidity_pool_withdraw(pool_id, one, one, one)
+            .account_merge(DESTINATION)
+            .build()
+            .unwrap();
+
+        assert_eq!(23, tx.operations().len());
+        assert!(tx.operations()[14].source_account().is_some());
+        assert!(tx.to_envelope().xdr_base64().is_ok());
+    }
+
+    #[test]
+    fn test_build_keeps_first_error() {
+        let mut account = account(1);
+
+        let result = TransactionBuilder::new(&mut account, 100)
+            .payment("GINVALID", &Asset::native(), Amount::ZERO)
+            .claim_claimable_balance("not hex")
+            .build();
+
+        assert!(matches!(result, Err(Error::InvalidStrKey(_))));
+        assert_eq!("1", account.sequence);
+    }
+
+    #[test]
+    fn test_build_without_operations() {
+        let mut account = account(1);
+
+        let result = TransactionBuilder::new(&mut account, 100).build();
+
+        assert!(matches!(result, Err(Error::Xdr(_))));
+        assert_eq!("1", account.sequence);
+    }
+
+    #[cfg(feature = "nacl")]
+    #[test]
+    fn test_build_and_sign() {
+        let mut account = account(1);
+        let keypair =
+            Keypair::from_secret_key("SAZ443I6BNR2MD3G27C4EZIEEFMKOPT4SR6IHZDLXPODEHR2GRQVIC7R")
+                .unwrap();
+
+        let tx = TransactionBuilder::new(&mut account, 100)
+            .bump_sequence(5)
+            .build_and_sign(&[&keypair], &Network::new_test())
+            .unwrap();
+
+        assert_eq!(1, tx.signatures().len());
+    }
+}
@@ -1,7 +1,9 @@
-use anyhow::bail;
 use byteorder::{ByteOrder, LittleEndian};
 use crc::{Crc, CRC_16_XMODEM};
 use data_encoding::BASE32;
+use std::fmt::{Display, Formatter};
+
+use crate::Error;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 enum VersionBytes {
@@ -13,22 +15,24 @@ enum VersionBytes {
     SignedPayload,     // P
 }
 
-impl ToString for VersionBytes {
-    fn to_string(&self) -> String {
-        match self {
-            VersionBytes::Ed25519PublicKey => String::from("ed25519PublicKey"),
-            VersionBytes::Ed25519SecretSeed => String::from("ed25519SecretSeed"),
-            VersionBytes::Med25519PublicKey => String::from("med25519PublicKey"),
-            VersionBytes::PreAuthTx => String::from("preAuthTx"),
-            VersionBytes::Sha256Hash => String::from("sha256Hash"),
-            VersionBytes::SignedPayload => String::from("signedPayload"),
-        }
+impl Display for VersionBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            VersionBytes::Ed25519PublicKey => "ed25519PublicKey",
+            VersionBytes::Ed25519SecretSeed => "ed25519SecretSeed",
+            VersionBytes::Med25519PublicKey => "med25519PublicKey",
+            VersionBytes::PreAuthTx => "preAuthTx",
+            VersionBytes::Sha256Hash => "sha256Hash",
+            VersionBytes::SignedPayload => "signedPayload",
+        };
+
+        f.write_str(name)
     }
 }
 
-impl Into<u8> for VersionBytes {
-    fn into(self) -> u8 {
-        match self {
+impl From<VersionBytes> for u8 {
+    fn from(v: VersionBytes) -> u8 {
+        match v {
             VersionBytes::Ed25519PublicKey => 6 << 3,
             VersionBytes::Ed25519SecretSeed => 18 << 3,
             VersionBytes::Med25519PublicKey => 12 << 3,
@@ -40,7 +44,7 @@ impl Into<u8> for VersionBytes {
 }
 
 impl TryFrom<u8> for VersionBytes {
-    type Error = anyhow::Error;
+    type Error = crate::Error;
 
     fn try_from(version_bye: u8) -> Result<Self, Self::Error> {
         match version_bye {
@@ -50,13 +54,13 @@ impl TryFrom<u8> for VersionBytes {
             152 => Ok(VersionBytes::PreAuthTx),         //  19 << 3 == 152
             184 => Ok(VersionBytes::Sha256Hash),        //  23 << 3 == 184
             120 => Ok(VersionBytes::SignedPayload),     //  15 << 3 == 120
-            _ => bail!("invalid version byte"),
+            _ => Err(Error::InvalidStrKey("invalid version byte")),
         }
     }
 }
 
 impl TryFrom<char> for VersionBytes {
-    type Error = anyhow::Error;
+    type Error = crate::Error;
 
     fn try_from(version_bye: char) -> Result<Self, Self::Error> {
         match version_bye {
@@ -66,7 +70,7 @@ impl TryFrom<char> for VersionBytes {
             'T' => Ok(VersionBytes::PreAuthTx),
             'X' => Ok(VersionBytes::Sha256Hash),
             'P' => Ok(VersionBytes::SignedPayload),
-            _ => bail!("invalid version byte"),
+            _ => Err(Error::InvalidStrKey("invalid version byte")),
         }
     }
 }
@@ -88,24 +92,29 @@ fn encode_check(v: &VersionBytes, data: &[u8]) -> String {
     BASE32.encode(&bytes)
 }
 
-fn decode_check(v: &VersionBytes, encoded_data: &str) -> Result<Vec<u8>, anyhow::Error> {
-    let decoded = BASE32.decode(encoded_data.as_bytes())?;
+fn decode_check(v: &VersionBytes, encoded_data: &str) -> Result<Vec<u8>, Error> {
+    let decoded = BASE32
+        .decode(encoded_data.as_bytes())
+        .map_err(|_| Error::InvalidStrKey("invalid encode string"))?;
+    if decoded.len() < 3 {
+        return Err(Error::InvalidStrKey("invalid encode string"));
+    }
     let version_byte: VersionBytes = VersionBytes::try_from(decoded[0])?;
     let payload = &decoded[..decoded.len() - 2];
     let data = &payload[1..];
     let checksum = &decoded[decoded.len() - 2..];
 
     if encoded_data != BASE32.encode(&decoded) {
-        bail!("invalid encode string")
+        return Err(Error::InvalidStrKey("invalid encode string"));
     }
 
     if &version_byte != v {
-        bail!("invalid version byte")
+        return Err(Error::InvalidStrKey("invalid version byte"));
     }
 
     let expected_checksum = calculate_checksum(payload);
     if expected_checksum != checksum {
-        bail!("invalid checksum")
+        return Err(Error::InvalidStrKey("invalid checksum"));
     }
 
     Ok(data.to_vec())
@@ -155,7 +164,7 @@ impl StrKey {
         encode_check(&VersionBytes::Ed25519PublicKey, data)
     }
 
-    pub fn decode_ed25519_public_key(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn decode_ed25519_public_key(data: &str) -> Result<Vec<u8>, Error> {
         decode_check(&VersionBytes::Ed25519PublicKey, data)
     }
 
@@ -167,7 +176,7 @@ impl StrKey {
         encode_check(&VersionBytes::Ed25519SecretSeed, data)
     }
 
-    pub fn decode_ed25519_secret_seed(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn decode_ed25519_secret_seed(data: &str) -> Result<Vec<u8>, Error> {
         decode_check(&VersionBytes::Ed25519SecretSeed, data)
     }
 
@@ -179,7 +188,7 @@ impl StrKey {
         encode_check(&VersionBytes::Med25519PublicKey, data)
     }
 
-    pub fn decode_med25519_public_key(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn decode_med25519_public_key(data: &str) -> Result<Vec<u8>, Error> {
         decode_check(&VersionBytes::Med25519PublicKey, data)
     }
 
@@ -191,7 +200,7 @@ impl StrKey {
         encode_check(&VersionBytes::PreAuthTx, data)
     }
 
-    pub fn decode_pre_auth_tx(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn decode_pre_auth_tx(data: &str) -> Result<Vec<u8>, Error> {
         decode_check(&VersionBytes::PreAuthTx, data)
     }
 
@@ -203,7 +212,7 @@ impl StrKey {
         encode_check(&VersionBytes::Sha256Hash, data)
     }
 
-    pub fn decode_sha256_hash(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn decode_sha256_hash(data: &str) -> Result<Vec<u8>, Error> {
         decode_check(&VersionBytes::Sha256Hash, data)
     }
 
@@ -215,7 +224,7 @@ impl StrKey {
         encode_check(&VersionBytes::SignedPayload, data)
     }
 
-    pub fn decode_signed_payload(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    pub fn decode_signed_payload(data: &str) -> Result<Vec<u8>, Error> {
         decode_check(&VersionBytes::SignedPayload, data)
     }
 
@@ -223,10 +232,15 @@ impl StrKey {
         is_valid(&VersionBytes::SignedPayload, data)
     }
 
-    pub fn get_version_byte_for_prefix(data: &str) -> Result<String, anyhow::Error> {
-        let decoded = BASE32.decode(data.as_bytes())?;
+    pub fn get_version_byte_for_prefix(data: &str) -> Result<String, Error> {
+        let decoded = BASE32
+            .decode(data.as_bytes())
+            .map_err(|_| Error::InvalidStrKey("invalid encode string"))?;
+        let version_byte = decoded
+            .first()
+            .ok_or(Error::InvalidStrKey("invalid encode string"))?;
 
-        Ok(VersionBytes::try_from(decoded[0])?.to_string())
+        Ok(VersionBytes::try_from(*version_byte)?.to_string())
     }
 }
 
@@ -246,7 +260,7 @@ mod tests {
     fn test_encode() {
         let encoded_public = encode_check(
             &VersionBytes::Ed25519PublicKey,
-            &mut [
+            &[
                 91u8, 49, 118, 218, 79, 232, 118, 216, 114, 82, 9, 175, 17, 217, 95, 50, 155, 52,
                 15, 112, 137, 99, 101, 172, 40, 104, 207, 154, 154, 33, 113, 92,
             ],
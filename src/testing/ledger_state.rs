@@ -0,0 +1,544 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use data_encoding::{BASE64, HEXLOWER};
+use sodiumoxide::crypto::sign::ed25519 as sodium;
+use stellar_base::crypto::{hash, Ed25519Verifier, MuxedAccount, PublicKey, SignatureHint};
+use stellar_base::memo::Memo;
+use stellar_base::operations::Operation as OperationSBase;
+use stellar_base::transaction::{Transaction as TransactionSBase, TransactionEnvelope};
+use stellar_base::xdr::{self, XDRDeserialize};
+use stellar_base::Network;
+use xdr_rs_serialize::ser::XDROut;
+
+use crate::types::{OperationResultCode, TransactionResultCode};
+
+// The base fee and base reserve of the public network
+pub(crate) const BASE_FEE: i64 = 100;
+pub(crate) const BASE_RESERVE: i64 = 5_000_000;
+// An account without subentries has to keep two base reserves
+const MIN_BALANCE: i64 = 2 * BASE_RESERVE;
+
+#[derive(Debug, Clone)]
+pub(crate) struct AccountEntry {
+    pub(crate) balance: i64,
+    pub(crate) sequence: i64,
+    pub(crate) last_modified_ledger: u32,
+    pub(crate) last_modified_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LedgerEntry {
+    pub(crate) sequence: u32,
+    pub(crate) hash: String,
+    pub(crate) prev_hash: String,
+    pub(crate) closed_at: DateTime<Utc>,
+    pub(crate) successful_transaction_count: u32,
+    pub(crate) failed_transaction_count: u32,
+    pub(crate) operation_count: u32,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TransactionEntry {
+    // The total order ID Horizon derives the paging token from
+    pub(crate) toid: i64,
+    pub(crate) hash: String,
+    pub(crate) ledger: u32,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) source_account: String,
+    pub(crate) source_account_sequence: i64,
+    pub(crate) fee_charged: i64,
+    pub(crate) max_fee: i64,
+    pub(crate) operation_count: usize,
+    pub(crate) envelope_xdr: String,
+    pub(crate) result_xdr: String,
+    pub(crate) memo_type: &'static str,
+    pub(crate) memo: Option<String>,
+    pub(crate) signatures: Vec<String>,
+    pub(crate) valid_after: Option<DateTime<Utc>>,
+    pub(crate) valid_before: Option<DateTime<Utc>>,
+    pub(crate) successful: bool,
+    pub(crate) operation_codes: Vec<OperationResultCode>,
+    // Every account the transaction touched
+    pub(crate) participants: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OperationEntry {
+    pub(crate) toid: i64,
+    pub(crate) ledger: u32,
+    pub(crate) transaction_hash: String,
+    pub(crate) transaction_successful: bool,
+    pub(crate) source_account: String,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) body: OperationBody,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum OperationBody {
+    CreateAccount {
+        account: String,
+        starting_balance: i64,
+    },
+    Payment {
+        to: String,
+        amount: i64,
+    },
+}
+
+/// What became of a submitted transaction.
+#[derive(Debug)]
+pub(crate) enum Submission {
+    /// The envelope could not be decoded.
+    Malformed,
+    /// The transaction made it into a ledger, whether its operations succeeded or
+    /// not. Holds its index in `transactions`.
+    Included(usize),
+    /// The transaction was refused before it could make it into a ledger.
+    Rejected {
+        code: TransactionResultCode,
+        result_xdr: String,
+    },
+}
+
+/// The accounts, ledgers, transactions and operations of a fake Horizon. Only
+/// native balances exist, and a ledger closes for every transaction submitted.
+#[derive(Debug)]
+pub(crate) struct LedgerState {
+    pub(crate) network: Network,
+    pub(crate) accounts: BTreeMap<String, AccountEntry>,
+    pub(crate) ledgers: Vec<LedgerEntry>,
+    pub(crate) transactions: Vec<TransactionEntry>,
+    pub(crate) operations: Vec<OperationEntry>,
+}
+
+impl LedgerState {
+    pub(crate) fn new(network: Network) -> Self {
+        let mut state = Self {
+            network,
+            accounts: BTreeMap::new(),
+            ledgers: Vec::new(),
+            transactions: Vec::new(),
+            operations: Vec::new(),
+        };
+        state.close_ledger(0, 0, 0);
+
+        state
+    }
+
+    pub(crate) fn latest_ledger(&self) -> &LedgerEntry {
+        self.ledgers
+            .last()
+            .expect("the genesis ledger is always there")
+    }
+
+    /// Creates the account, or adds to its balance when it exists, like friendbot
+    /// but without a transaction.
+    pub(crate) fn fund(&mut self, account_id: &str, amount: i64) {
+        let ledger = self.latest_ledger().clone();
+
+        let account = self
+            .accounts
+            .entry(String::from(account_id))
+            .or_insert(AccountEntry {
+                balance: 0,
+                // A new account starts with the sequence number of its ledger
+                sequence: i64::from(ledger.sequence) << 32,
+                last_modified_ledger: ledger.sequence,
+                last_modified_time: ledger.closed_at,
+            });
+        account.balance = account.balance.saturating_add(amount);
+    }
+
+    fn close_ledger(&mut self, successful: u32, failed: u32, operations: u32) -> LedgerEntry {
+        let sequence = self.ledgers.len() as u32 + 1;
+        let prev_hash = self
+            .ledgers
+            .last()
+            .map(|ledger| ledger.hash.clone())
+            .unwrap_or_else(|| HEXLOWER.encode(&[0; 32]));
+
+        let ledger = LedgerEntry {
+            sequence,
+            hash: HEXLOWER.encode(&hash(format!("{}{}", prev_hash, sequence).as_bytes())),
+            prev_hash,
+            closed_at: Utc::now().trunc_subsecs(0),
+            successful_transaction_count: successful,
+            failed_transaction_count: failed,
+            operation_count: operations,
+        };
+        self.ledgers.push(ledger.clone());
+
+        ledger
+    }
+
+    /// Validates the transaction the way Stellar Core would and applies it in a new
+    /// ledger. Fee bump transactions are not supported.
+    pub(crate) fn submit(&mut self, envelope_xdr: &str) -> Submission {
+        let envelope = match TransactionEnvelope::from_xdr_base64(envelope_xdr) {
+            Ok(envelope) => envelope,
+            Err(_) => return Submission::Malformed,
+        };
+        let hash = match envelope.hash(&self.network) {
+            Ok(hash) => hash,
+            Err(_) => return Submission::Malformed,
+        };
+        let tx = match envelope.as_transaction() {
+            Some(tx) => tx,
+            None => return rejected(TransactionResultCode::NotSupported),
+        };
+
+        // Submitting the same transaction again gives back its result
+        let hex_hash = HEXLOWER.encode(&hash);
+        if let Some(index) = self.transactions.iter().position(|t| t.hash == hex_hash) {
+            return Submission::Included(index);
+        }
+
+        if let Err(code) = self.check_transaction(tx, &hash) {
+            return rejected(code);
+        }
+
+        let source = account_id(tx.source_account());
+        let operation_count = tx.operations().len();
+        let fee_charged = BASE_FEE * operation_count as i64;
+        let ledger = self.latest_ledger().sequence + 1;
+        let created_at = Utc::now().trunc_subsecs(0);
+
+        // The fee is charged and the sequence number used even when an operation fails
+        let account = self.accounts.get_mut(&source).expect("checked above");
+        account.balance -= fee_charged;
+        account.sequence = *tx.sequence();
+        account.last_modified_ledger = ledger;
+        account.last_modified_time = created_at;
+
+        // The operations are applied to a copy, kept only when all of them succeed
+        let mut accounts = self.accounts.clone();
+        let mut bodies = Vec::new();
+        let mut codes = Vec::new();
+        let mut results = Vec::new();
+        let mut participants = BTreeSet::from([source.clone()]);
+        for operation in tx.operations() {
+            let op_source = operation
+                .source_account()
+                .as_ref()
+                .map(account_id)
+                .unwrap_or_else(|| source.clone());
+            let body = operation_body(operation);
+
+            let code = match &body {
+                Some(body) => apply_operation(&mut accounts, &op_source, body, ledger)
+                    .err()
+                    .unwrap_or(OperationResultCode::Success),
+                None => OperationResultCode::NotSupported,
+            };
+            results.push(operation_result(body.as_ref(), &code));
+            codes.push(code);
+
+            participants.insert(op_source.clone());
+            if let Some(body) = body {
+                participants.insert(body.destination().to_string());
+                bodies.push((op_source, body));
+            }
+        }
+
+        let successful = codes
+            .iter()
+            .all(|code| *code == OperationResultCode::Success);
+        if successful {
+            for participant in participants.iter() {
+                if let Some(account) = accounts.get_mut(participant) {
+                    account.last_modified_ledger = ledger;
+                    account.last_modified_time = created_at;
+                }
+            }
+            self.accounts = accounts;
+        }
+
+        let result = if successful {
+            xdr::TransactionResultResult::TxSuccess(results)
+        } else {
+            xdr::TransactionResultResult::TxFailed(results)
+        };
+        let ledger_entry = self.close_ledger(
+            u32::from(successful),
+            u32::from(!successful),
+            operation_count as u32,
+        );
+
+        let toid = (i64::from(ledger_entry.sequence) << 32) | (1 << 12);
+        for (index, (op_source, body)) in bodies.into_iter().enumerate() {
+            self.operations.push(OperationEntry {
+                toid: toid + index as i64 + 1,
+                ledger: ledger_entry.sequence,
+                transaction_hash: hex_hash.clone(),
+                transaction_successful: successful,
+                source_account: op_source,
+                created_at: ledger_entry.closed_at,
+                body,
+            });
+        }
+
+        let (memo_type, memo) = memo(tx.memo());
+        let time_bounds = tx.time_bounds().as_ref();
+        self.transactions.push(TransactionEntry {
+            toid,
+            hash: hex_hash,
+            ledger: ledger_entry.sequence,
+            created_at: ledger_entry.closed_at,
+            source_account: source,
+            source_account_sequence: *tx.sequence(),
+            fee_charged,
+            max_fee: tx.fee().to_i64(),
+            operation_count,
+            envelope_xdr: String::from(envelope_xdr),
+            result_xdr: transaction_result_xdr(fee_charged, result),
+            memo_type,
+            memo,
+            signatures: tx
+                .signatures()
+                .iter()
+                .map(|signature| BASE64.encode(&signature.signature().to_bytes()))
+                .collect(),
+            valid_after: time_bounds.and_then(|bounds| *bounds.lower()),
+            valid_before: time_bounds.and_then(|bounds| *bounds.upper()),
+            successful,
+            operation_codes: codes,
+            participants,
+        });
+
+        Submission::Included(self.transactions.len() - 1)
+    }
+
+    // The checks that keep a transaction out of the ledger altogether
+    fn check_transaction(
+        &self,
+        tx: &TransactionSBase,
+        hash: &[u8],
+    ) -> Result<(), TransactionResultCode> {
+        let operation_count = tx.operations().len() as i64;
+        if operation_count == 0 {
+            return Err(TransactionResultCode::MissingOperation);
+        }
+        if tx.fee().to_i64() < BASE_FEE * operation_count {
+            return Err(TransactionResultCode::InsufficientFee);
+        }
+
+        let now = Utc::now();
+        if let Some(time_bounds) = tx.time_bounds() {
+            if time_bounds.lower().is_some_and(|lower| now < lower) {
+                return Err(TransactionResultCode::TooEarly);
+            }
+            if time_bounds.upper().is_some_and(|upper| now > upper) {
+                return Err(TransactionResultCode::TooLate);
+            }
+        }
+
+        let source = account_id(tx.source_account());
+        let account = self
+            .accounts
+            .get(&source)
+            .ok_or(TransactionResultCode::NoSourceAccount)?;
+        if *tx.sequence() != account.sequence + 1 {
+            return Err(TransactionResultCode::BadSeq);
+        }
+
+        // Every source account has to sign with its master key
+        let signers = tx
+            .operations()
+            .iter()
+            .filter_map(|operation| operation.source_account().as_ref())
+            .chain(std::iter::once(tx.source_account()));
+        for signer in signers {
+            if !is_signed_by(tx, hash, signer) {
+                return Err(TransactionResultCode::BadAuth);
+            }
+        }
+
+        if account.balance - BASE_FEE * operation_count < MIN_BALANCE {
+            return Err(TransactionResultCode::InsufficientBalance);
+        }
+
+        Ok(())
+    }
+}
+
+impl OperationBody {
+    pub(crate) fn destination(&self) -> &str {
+        match self {
+            OperationBody::CreateAccount { account, .. } => account,
+            OperationBody::Payment { to, .. } => to,
+        }
+    }
+}
+
+fn rejected(code: TransactionResultCode) -> Submission {
+    let result = match code {
+        TransactionResultCode::TooEarly => xdr::TransactionResultResult::TxTooEarly(()),
+        TransactionResultCode::TooLate => xdr::TransactionResultResult::TxTooLate(()),
+        TransactionResultCode::MissingOperation => {
+            xdr::TransactionResultResult::TxMissingOperation(())
+        }
+        TransactionResultCode::BadSeq => xdr::TransactionResultResult::TxBadSeq(()),
+        TransactionResultCode::BadAuth => xdr::TransactionResultResult::TxBadAuth(()),
+        TransactionResultCode::InsufficientBalance => {
+            xdr::TransactionResultResult::TxInsufficientBalance(())
+        }
+        TransactionResultCode::NoSourceAccount => xdr::TransactionResultResult::TxNoAccount(()),
+        TransactionResultCode::InsufficientFee => {
+            xdr::TransactionResultResult::TxInsufficientFee(())
+        }
+        _ => xdr::TransactionResultResult::TxNotSupported(()),
+    };
+
+    Submission::Rejected {
+        code,
+        result_xdr: transaction_result_xdr(0, result),
+    }
+}
+
+fn transaction_result_xdr(fee_charged: i64, result: xdr::TransactionResultResult) -> String {
+    let mut bytes = Vec::new();
+    xdr::TransactionResult {
+        fee_charged: xdr::Int64::new(fee_charged),
+        result,
+        ext: xdr::TransactionResultExt::V0(()),
+    }
+    .write_xdr(&mut bytes)
+    .expect("transaction results always encode");
+
+    BASE64.encode(&bytes)
+}
+
+fn account_id(account: &MuxedAccount) -> String {
+    match account {
+        MuxedAccount::Ed25519(public_key) => public_key.account_id(),
+        MuxedAccount::MuxedEd25519(muxed) => muxed.public_key().account_id(),
+    }
+}
+
+fn public_key(account: &MuxedAccount) -> &PublicKey {
+    match account {
+        MuxedAccount::Ed25519(public_key) => public_key,
+        MuxedAccount::MuxedEd25519(muxed) => muxed.public_key(),
+    }
+}
+
+fn is_signed_by(tx: &TransactionSBase, hash: &[u8], account: &MuxedAccount) -> bool {
+    let public_key = public_key(account);
+    let hint = SignatureHint::from_public_key(public_key.as_bytes());
+    let verify_key = match sodium::PublicKey::from_slice(public_key.as_bytes()) {
+        Some(verify_key) => verify_key,
+        None => return false,
+    };
+
+    tx.signatures().iter().any(|signature| {
+        *signature.hint() == hint && verify_key.verify(hash, signature.signature()).is_ok()
+    })
+}
+
+// The operations the fake applies, None for the others
+fn operation_body(operation: &OperationSBase) -> Option<OperationBody> {
+    match operation {
+        OperationSBase::CreateAccount(op) => Some(OperationBody::CreateAccount {
+            account: op.destination().account_id(),
+            starting_balance: op.starting_balance().to_i64(),
+        }),
+        OperationSBase::Payment(op) if op.asset().is_native() => Some(OperationBody::Payment {
+            to: account_id(op.destination()),
+            amount: op.amount().to_i64(),
+        }),
+        _ => None,
+    }
+}
+
+fn apply_operation(
+    accounts: &mut BTreeMap<String, AccountEntry>,
+    source: &str,
+    body: &OperationBody,
+    ledger: u32,
+) -> Result<(), OperationResultCode> {
+    let source_balance = accounts
+        .get(source)
+        .ok_or(OperationResultCode::NoSourceAccount)?
+        .balance;
+
+    let amount = match body {
+        OperationBody::CreateAccount {
+            account,
+            starting_balance,
+        } => {
+            if accounts.contains_key(account) {
+                return Err(OperationResultCode::AlreadyExists);
+            }
+            if *starting_balance < MIN_BALANCE {
+                return Err(OperationResultCode::LowReserve);
+            }
+            *starting_balance
+        }
+        OperationBody::Payment { to, amount } => {
+            if !accounts.contains_key(to) {
+                return Err(OperationResultCode::NoDestination);
+            }
+            *amount
+        }
+    };
+    if source_balance - amount < MIN_BALANCE {
+        return Err(OperationResultCode::Underfunded);
+    }
+
+    let destination = body.destination();
+    accounts.get_mut(source).expect("checked above").balance -= amount;
+    match accounts.get_mut(destination) {
+        Some(account) => account.balance += amount,
+        None => {
+            // The modification time is set once the ledger closes
+            accounts.insert(
+                String::from(destination),
+                AccountEntry {
+                    balance: amount,
+                    sequence: i64::from(ledger) << 32,
+                    last_modified_ledger: ledger,
+                    last_modified_time: Utc::now().trunc_subsecs(0),
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn operation_result(
+    body: Option<&OperationBody>,
+    code: &OperationResultCode,
+) -> xdr::OperationResult {
+    use xdr::{CreateAccountResult as Create, OperationResultTr as Tr, PaymentResult as Pay};
+
+    let inner = match (body, code) {
+        (_, OperationResultCode::NoSourceAccount) => return xdr::OperationResult::OpNoAccount(()),
+        (None, _) => return xdr::OperationResult::OpNotSupported(()),
+        (Some(OperationBody::CreateAccount { .. }), code) => Tr::CreateAccount(match code {
+            OperationResultCode::AlreadyExists => Create::CreateAccountAlreadyExist(()),
+            OperationResultCode::LowReserve => Create::CreateAccountLowReserve(()),
+            OperationResultCode::Underfunded => Create::CreateAccountUnderfunded(()),
+            _ => Create::CreateAccountSuccess(()),
+        }),
+        (Some(OperationBody::Payment { .. }), code) => Tr::Payment(match code {
+            OperationResultCode::NoDestination => Pay::PaymentNoDestination(()),
+            OperationResultCode::Underfunded => Pay::PaymentUnderfunded(()),
+            _ => Pay::PaymentSuccess(()),
+        }),
+    };
+
+    xdr::OperationResult::OpInner(inner)
+}
+
+// The memo type and value as Horizon shows them
+fn memo(memo: &Memo) -> (&'static str, Option<String>) {
+    match memo {
+        Memo::None => ("none", None),
+        Memo::Text(text) => ("text", Some(text.clone())),
+        Memo::Id(id) => ("id", Some(id.to_string())),
+        Memo::Hash(hash) => ("hash", Some(BASE64.encode(hash))),
+        Memo::Return(hash) => ("return", Some(BASE64.encode(hash))),
+    }
+}
@@ -0,0 +1,437 @@
+//! An in-process fake of Horizon for integration tests, behind the `testing` feature.
+
+mod ledger_state;
+mod routes;
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use chrono::Utc;
+use percent_encoding::percent_decode_str;
+use stellar_base::{Network, PublicKey};
+use url::{form_urlencoded, Url};
+
+use crate::testing::ledger_state::LedgerState;
+use crate::testing::routes::{Request, Response};
+use crate::types::Amount;
+use crate::{Error, Server, ServerOptions};
+
+/// A fake Horizon listening on `127.0.0.1`, backed by an in-memory ledger, so that a
+/// [`Server`] can be tested end to end without testnet.
+///
+/// It serves accounts, ledgers, transactions, operations and payments, fee stats,
+/// paths and order books. Submitted transactions are checked for their sequence
+/// number, fee, time bounds and signatures, then applied in a ledger of their own.
+/// Only native payments and account creations are applied, every other operation
+/// fails with `op_not_supported`. As nothing but lumens exists, order books are
+/// empty and the only paths are direct native payments.
+///
+/// The server stops when dropped.
+///
+/// ```
+/// use stellar_sdk::testing::FakeHorizon;
+///
+/// let account_id = "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM";
+/// let horizon = FakeHorizon::start().unwrap();
+/// horizon.fund(account_id, "100".parse().unwrap()).unwrap();
+///
+/// let account = horizon.server().load_account(account_id).unwrap();
+/// assert_eq!("100.0000000", account.balances[0].balance.to_string());
+/// ```
+#[derive(Debug)]
+pub struct FakeHorizon {
+    url: String,
+    address: SocketAddr,
+    state: Arc<Mutex<LedgerState>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FakeHorizon {
+    /// Starts a fake of the test network on a free port.
+    pub fn start() -> Result<Self, Error> {
+        Self::with_network(Network::new_test())
+    }
+
+    /// Starts a fake whose transactions have to be signed for `network`.
+    pub fn with_network(network: Network) -> Result<Self, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let url = format!("http://{}", address);
+        let state = Arc::new(Mutex::new(LedgerState::new(network)));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let (url, state, shutdown) = (url.clone(), state.clone(), shutdown.clone());
+            thread::spawn(move || serve(listener, &url, &state, &shutdown))
+        };
+
+        Ok(Self {
+            url,
+            address,
+            state,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// The base URL, e.g. `http://127.0.0.1:41213`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A [`Server`] talking to this fake over plain http.
+    pub fn server(&self) -> Server {
+        let options = ServerOptions {
+            allow_http: Some(true),
+            ..Default::default()
+        };
+
+        Server::new(self.url.clone(), Some(options)).expect("the fake url is valid")
+    }
+
+    /// Creates the account with `amount` lumens, or adds them to its balance, like
+    /// friendbot does on testnet.
+    pub fn fund(&self, account_id: &str, amount: Amount) -> Result<(), Error> {
+        PublicKey::from_account_id(account_id)
+            .map_err(|_| Error::InvalidStrKey("invalid account id"))?;
+
+        self.state
+            .lock()
+            .unwrap()
+            .fund(account_id, amount.to_stroops());
+
+        Ok(())
+    }
+}
+
+impl Drop for FakeHorizon {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wakes up the accept loop so that it sees the flag
+        let _ = TcpStream::connect(self.address);
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve(listener: TcpListener, url: &str, state: &Arc<Mutex<LedgerState>>, shutdown: &AtomicBool) {
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        let (url, state) = (String::from(url), state.clone());
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &url, &state);
+        });
+    }
+}
+
+// Answers a single request and closes the connection
+fn handle_connection(stream: TcpStream, url: &str, state: &Mutex<LedgerState>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader)? {
+        Some(request) => routes::handle(&mut state.lock().unwrap(), url, &request),
+        None => return Ok(()),
+    };
+
+    write_response(stream, &response)
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<Option<Request>> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let url = match Url::parse("http://localhost").and_then(|base| base.join(target)) {
+        Ok(url) => url,
+        Err(_) => return Ok(None),
+    };
+    let segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| percent_decode_str(segment).decode_utf8_lossy().into_owned())
+        .collect();
+    let params = url
+        .query_pairs()
+        .chain(form_urlencoded::parse(&body))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect::<HashMap<String, String>>();
+
+    Ok(Some(Request {
+        method: String::from(method),
+        segments,
+        params,
+    }))
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    let content_type = match response.status {
+        200 => "application/hal+json; charset=utf-8",
+        _ => "application/problem+json; charset=utf-8",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nDate: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        content_type,
+        body.len(),
+        Utc::now().format("%a, %d %b %Y %H:%M:%S GMT"),
+        body
+    )?;
+
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use stellar_base::{
+        amount::Amount as BaseAmount, asset::Asset as BaseAsset, crypto::SodiumKeyPair,
+        operations::Operation, transaction::Transaction, transaction::MIN_BASE_FEE,
+    };
+
+    use crate::types::{
+        Asset, OperationKind, OperationResultCode, StrictPathSource, TransactionResultCode,
+    };
+    use crate::utils::Endpoint;
+    use crate::CallBuilder;
+
+    fn xlm(amount: &str) -> Amount {
+        Amount::from_str(amount).unwrap()
+    }
+
+    fn signed(source: &SodiumKeyPair, sequence: i64, operations: Vec<Operation>) -> Transaction {
+        let mut builder = Transaction::builder(source.public_key(), sequence, MIN_BASE_FEE);
+        for operation in operations {
+            builder = builder.add_operation(operation);
+        }
+        let mut tx = builder.into_transaction().unwrap();
+        tx.sign(source.as_ref(), &Network::new_test()).unwrap();
+
+        tx
+    }
+
+    fn payment(destination: &SodiumKeyPair, amount: &str) -> Operation {
+        Operation::new_payment()
+            .with_destination(destination.public_key())
+            .with_amount(BaseAmount::from_str(amount).unwrap())
+            .unwrap()
+            .with_asset(BaseAsset::new_native())
+            .build()
+            .unwrap()
+    }
+
+    fn next_sequence(s: &Server, account: &SodiumKeyPair) -> i64 {
+        let account = s.load_account(&account.public_key().account_id()).unwrap();
+
+        account.sequence.parse::<i64>().unwrap() + 1
+    }
+
+    #[test]
+    fn test_submit_payment_and_create_account() {
+        let horizon = FakeHorizon::start().unwrap();
+        let s = horizon.server();
+        let alice = SodiumKeyPair::random().unwrap();
+        let bob = SodiumKeyPair::random().unwrap();
+        horizon
+            .fund(&alice.public_key().account_id(), xlm("100"))
+            .unwrap();
+
+        let create_account = Operation::new_create_account()
+            .with_destination(bob.public_key())
+            .with_starting_balance(BaseAmount::from_str("20").unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let response = s
+            .submit_transaction(signed(
+                &alice,
+                next_sequence(&s, &alice),
+                vec![create_account],
+            ))
+            .unwrap();
+        assert!(response.successful);
+
+        s.submit_transaction(signed(
+            &alice,
+            next_sequence(&s, &alice),
+            vec![payment(&bob, "5")],
+        ))
+        .unwrap();
+
+        let alice_account = s.load_account(&alice.public_key().account_id()).unwrap();
+        let bob_account = s.load_account(&bob.public_key().account_id()).unwrap();
+        assert_eq!(xlm("74.9999800"), alice_account.balances[0].balance);
+        assert_eq!(xlm("25"), bob_account.balances[0].balance);
+
+        let transaction = s.load_transaction(&response.hash).unwrap();
+        assert_eq!(response.ledger as u64, transaction.ledger);
+        assert_eq!(3, s.load_ledger(transaction.ledger + 1).unwrap().sequence);
+
+        let payments = s
+            .operations()
+            .for_endpoint(Endpoint::Accounts(bob.public_key().account_id()))
+            .call()
+            .unwrap();
+        assert_eq!(2, payments._embedded.records.len());
+        assert!(matches!(
+            payments._embedded.records[1].kind,
+            OperationKind::Payment(ref payment) if payment.amount == xlm("5")
+        ));
+    }
+
+    #[test]
+    fn test_submit_failures() {
+        let horizon = FakeHorizon::start().unwrap();
+        let s = horizon.server();
+        let alice = SodiumKeyPair::random().unwrap();
+        let bob = SodiumKeyPair::random().unwrap();
+        horizon
+            .fund(&alice.public_key().account_id(), xlm("10"))
+            .unwrap();
+        let sequence = next_sequence(&s, &alice);
+
+        let bad_seq = s
+            .submit_transaction(signed(&alice, sequence + 1, vec![payment(&bob, "1")]))
+            .unwrap_err();
+        assert_eq!(
+            TransactionResultCode::BadSeq,
+            bad_seq.result_codes().unwrap().transaction_code()
+        );
+
+        let unsigned = signed(&bob, sequence, vec![payment(&alice, "1")]);
+        let mut forged = signed(&alice, sequence, vec![payment(&bob, "1")]);
+        *forged.signatures_mut() = unsigned.signatures().clone();
+        let bad_auth = s.submit_transaction(forged).unwrap_err();
+        assert_eq!(
+            TransactionResultCode::BadAuth,
+            bad_auth.result_codes().unwrap().transaction_code()
+        );
+
+        // The destination does not exist yet, the fee is charged all the same
+        let failed = s
+            .submit_transaction(signed(&alice, sequence, vec![payment(&bob, "1")]))
+            .unwrap_err();
+        let codes = failed.result_codes().unwrap();
+        assert_eq!(TransactionResultCode::Failed, codes.transaction_code());
+        assert_eq!(
+            vec![OperationResultCode::NoDestination],
+            codes.operation_codes()
+        );
+        assert!(failed
+            .horizon_error()
+            .unwrap()
+            .extras
+            .as_ref()
+            .unwrap()
+            .result()
+            .unwrap()
+            .is_failed());
+        assert_eq!(sequence + 1, next_sequence(&s, &alice));
+        assert_eq!(
+            xlm("9.9999900"),
+            s.load_account(&alice.public_key().account_id())
+                .unwrap()
+                .balances[0]
+                .balance
+        );
+
+        assert!(matches!(
+            s.load_account(&bob.public_key().account_id()),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_pages_fee_stats_paths_and_order_book() {
+        let horizon = FakeHorizon::start().unwrap();
+        let s = horizon.server();
+        let alice = SodiumKeyPair::random().unwrap();
+        let bob = SodiumKeyPair::random().unwrap();
+        horizon
+            .fund(&alice.public_key().account_id(), xlm("100"))
+            .unwrap();
+        horizon
+            .fund(&bob.public_key().account_id(), xlm("100"))
+            .unwrap();
+
+        for _ in 0..3 {
+            let tx = signed(&alice, next_sequence(&s, &alice), vec![payment(&bob, "1")]);
+            s.submit_transaction(tx).unwrap();
+        }
+
+        let ledgers = s
+            .ledgers()
+            .limit(2)
+            .pages()
+            .map(|page| page.unwrap()._embedded.records.len())
+            .collect::<Vec<usize>>();
+        assert_eq!(vec![2, 2], ledgers);
+        let transactions = s.transactions().pages().records().count();
+        assert_eq!(3, transactions);
+
+        assert_eq!("100", s.fetch_base_fee().unwrap());
+
+        let paths = s
+            .strict_receive_paths(
+                &StrictPathSource::Account(alice.public_key().account_id()),
+                Asset::native(),
+                String::from("10"),
+            )
+            .call()
+            .unwrap();
+        assert_eq!("10.0000000", paths._embedded.records[0].source_amount);
+
+        let order_book = s
+            .order_books(Asset::native(), Asset::native())
+            .call()
+            .unwrap();
+        assert!(order_book.bids.is_empty());
+    }
+}
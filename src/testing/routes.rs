@@ -0,0 +1,660 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+use crate::endpoints::horizon::{AccountSigner, AccountThresholds, Balance, Flags, ResponseLink};
+use crate::testing::ledger_state::{
+    AccountEntry, LedgerEntry, LedgerState, OperationBody, OperationEntry, Submission,
+    TransactionEntry, BASE_FEE, BASE_RESERVE,
+};
+use crate::types::{Account, AccountLinks, Amount, Ledger, LedgerLinks, TransactionResultCode};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 200;
+
+/// A request as the fake Horizon sees it, with the path split into decoded segments
+/// and the query merged with any form body.
+#[derive(Debug)]
+pub(crate) struct Request {
+    pub(crate) method: String,
+    pub(crate) segments: Vec<String>,
+    pub(crate) params: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Response {
+    pub(crate) status: u16,
+    pub(crate) body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+}
+
+pub(crate) fn handle(state: &mut LedgerState, base_url: &str, request: &Request) -> Response {
+    let segments = request
+        .segments
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<&str>>();
+
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", []) => Ok(root(state)),
+        ("GET", ["accounts"]) => accounts(state, base_url, request),
+        ("GET", ["accounts", id]) => state
+            .accounts
+            .get(*id)
+            .map(|entry| Response::ok(json!(account(base_url, id, entry))))
+            .ok_or_else(not_found),
+        ("GET", ["ledgers"]) => page(
+            base_url,
+            "/ledgers",
+            request,
+            state
+                .ledgers
+                .iter()
+                .map(|entry| {
+                    (
+                        i64::from(entry.sequence) << 32,
+                        json!(ledger(base_url, entry)),
+                    )
+                })
+                .collect(),
+        ),
+        ("GET", ["ledgers", sequence]) => sequence
+            .parse::<u32>()
+            .ok()
+            .and_then(|sequence| state.ledgers.get(sequence.checked_sub(1)? as usize))
+            .map(|entry| Response::ok(json!(ledger(base_url, entry))))
+            .ok_or_else(not_found),
+        ("GET", ["transactions", hash]) => state
+            .transactions
+            .iter()
+            .find(|tx| tx.hash == *hash)
+            .map(|tx| Response::ok(transaction_json(base_url, tx)))
+            .ok_or_else(not_found),
+        ("POST", ["transactions"]) => submit(state, base_url, request),
+        ("GET", ["operations", id]) => state
+            .operations
+            .iter()
+            .find(|op| op.toid.to_string() == *id)
+            .map(|op| Response::ok(operation_json(base_url, op)))
+            .ok_or_else(not_found),
+        ("GET", ["fee_stats"]) => Ok(fee_stats(state)),
+        ("GET", ["paths", "strict-receive"]) => strict_receive_paths(state, base_url, request),
+        ("GET", ["paths", "strict-send"]) => strict_send_paths(state, base_url, request),
+        ("GET", ["order_book"]) => Ok(order_book(request)),
+        ("GET", [collection]) => collection_page(state, base_url, request, None, collection),
+        ("GET", [resource, id, collection]) => {
+            collection_page(state, base_url, request, Some((*resource, *id)), collection)
+        }
+        _ => Err(not_found()),
+    };
+
+    result.unwrap_or_else(|response| response)
+}
+
+fn root(state: &LedgerState) -> Response {
+    let latest = state.latest_ledger().sequence;
+
+    Response::ok(json!({
+        "horizon_version": "fake",
+        "core_latest_ledger": latest,
+        "history_latest_ledger": latest,
+        "history_elder_ledger": 1,
+        "network_passphrase": state.network.passphrase(),
+        "current_protocol_version": 20,
+    }))
+}
+
+// Transactions, operations and payments, of everything or of one account, ledger
+// or transaction
+fn collection_page(
+    state: &LedgerState,
+    base_url: &str,
+    request: &Request,
+    scope: Option<(&str, &str)>,
+    collection: &str,
+) -> Result<Response, Response> {
+    let include_failed = request
+        .params
+        .get("include_failed")
+        .is_some_and(|include_failed| include_failed == "true");
+    let path = match scope {
+        Some((resource, id)) => format!("/{}/{}/{}", resource, id, collection),
+        None => format!("/{}", collection),
+    };
+
+    match scope {
+        Some(("accounts", id)) if !state.accounts.contains_key(id) => return Err(not_found()),
+        Some(("accounts" | "ledgers" | "transactions", _)) | None => {}
+        Some(_) => return Err(not_found()),
+    }
+
+    let in_scope = |ledger: u32, hash: &str, participants: &[&str]| match scope {
+        None => true,
+        Some(("accounts", id)) => participants.contains(&id),
+        Some(("ledgers", sequence)) => ledger.to_string() == sequence,
+        Some((_, tx_hash)) => hash == tx_hash,
+    };
+
+    let records = match collection {
+        "transactions" => state
+            .transactions
+            .iter()
+            .filter(|tx| include_failed || tx.successful)
+            .filter(|tx| {
+                let participants = tx
+                    .participants
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                in_scope(tx.ledger, &tx.hash, &participants)
+            })
+            .map(|tx| (tx.toid, transaction_json(base_url, tx)))
+            .collect(),
+        "operations" | "payments" => state
+            .operations
+            .iter()
+            .filter(|op| include_failed || op.transaction_successful)
+            .filter(|op| {
+                in_scope(
+                    op.ledger,
+                    &op.transaction_hash,
+                    &[op.source_account.as_str(), op.body.destination()],
+                )
+            })
+            .map(|op| (op.toid, operation_json(base_url, op)))
+            .collect(),
+        _ => return Err(not_found()),
+    };
+
+    page(base_url, &path, request, records)
+}
+
+fn accounts(state: &LedgerState, base_url: &str, request: &Request) -> Result<Response, Response> {
+    page(
+        base_url,
+        "/accounts",
+        request,
+        state
+            .accounts
+            .iter()
+            .map(|(id, entry)| (id.clone(), json!(account(base_url, id, entry))))
+            .collect(),
+    )
+}
+
+// A page of records after the cursor, in the requested order, with the links that
+// lead to the next and previous pages
+fn page<K: Ord + FromStr + ToString>(
+    base_url: &str,
+    path: &str,
+    request: &Request,
+    mut records: Vec<(K, Value)>,
+) -> Result<Response, Response> {
+    let param = |name: &str| request.params.get(name).map(String::as_str);
+
+    let limit = match param("limit") {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+            _ => return Err(bad_request("limit must be between 1 and 200")),
+        },
+        None => DEFAULT_LIMIT,
+    };
+    let descending = match param("order") {
+        Some("desc") => true,
+        Some("asc") | None => false,
+        Some(_) => return Err(bad_request("order must be asc or desc")),
+    };
+    let cursor = match param("cursor").filter(|cursor| *cursor != "now") {
+        Some(cursor) => Some(
+            cursor
+                .parse::<K>()
+                .map_err(|_| bad_request("cursor is invalid"))?,
+        ),
+        None => None,
+    };
+
+    records.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if descending {
+        records.reverse();
+    }
+    let records = records
+        .into_iter()
+        .filter(|(token, _)| match &cursor {
+            Some(cursor) if descending => token < cursor,
+            Some(cursor) => token > cursor,
+            None => true,
+        })
+        .take(limit)
+        .collect::<Vec<(K, Value)>>();
+
+    let token = |record: Option<&(K, Value)>| {
+        record
+            .map(|(token, _)| token.to_string())
+            .or_else(|| param("cursor").map(String::from))
+            .unwrap_or_default()
+    };
+    let href = |cursor: &str, order: &str| {
+        json!({
+            "href": format!(
+                "{}{}?cursor={}&limit={}&order={}",
+                base_url, path, cursor, limit, order
+            )
+        })
+    };
+    let (order, reverse) = if descending {
+        ("desc", "asc")
+    } else {
+        ("asc", "desc")
+    };
+
+    Ok(Response::ok(json!({
+        "_links": {
+            "self": href(param("cursor").unwrap_or_default(), order),
+            "next": href(&token(records.last()), order),
+            "prev": href(&token(records.first()), reverse),
+        },
+        "_embedded": {
+            "records": records.into_iter().map(|(_, record)| record).collect::<Vec<Value>>(),
+        },
+    })))
+}
+
+fn submit(
+    state: &mut LedgerState,
+    base_url: &str,
+    request: &Request,
+) -> Result<Response, Response> {
+    let envelope_xdr = request
+        .params
+        .get("tx")
+        .ok_or_else(|| bad_request("tx is missing"))?;
+
+    match state.submit(envelope_xdr) {
+        Submission::Malformed => Err(problem(
+            400,
+            "transaction_malformed",
+            "Transaction Malformed",
+            json!({ "envelope_xdr": envelope_xdr }),
+        )),
+        Submission::Rejected { code, result_xdr } => Err(transaction_failed(
+            envelope_xdr,
+            code.as_str(),
+            None,
+            &result_xdr,
+        )),
+        Submission::Included(index) => {
+            let tx = &state.transactions[index];
+            if tx.successful {
+                return Ok(Response::ok(transaction_json(base_url, tx)));
+            }
+
+            let operations = tx
+                .operation_codes
+                .iter()
+                .map(|code| code.as_str())
+                .collect::<Vec<&str>>();
+            Err(transaction_failed(
+                envelope_xdr,
+                TransactionResultCode::Failed.as_str(),
+                Some(operations),
+                &tx.result_xdr,
+            ))
+        }
+    }
+}
+
+fn fee_stats(state: &LedgerState) -> Response {
+    let fee = BASE_FEE.to_string();
+    let distribution = [
+        "max", "min", "mode", "p10", "p20", "p30", "p40", "p50", "p60",
+    ]
+    .iter()
+    .chain(["p70", "p80", "p90", "p95", "p99"].iter())
+    .map(|name| (String::from(*name), Value::from(fee.clone())))
+    .collect::<serde_json::Map<String, Value>>();
+
+    Response::ok(json!({
+        "last_ledger": state.latest_ledger().sequence.to_string(),
+        "last_ledger_base_fee": fee,
+        "ledger_capacity_usage": "0.00",
+        "fee_charged": distribution,
+        "max_fee": distribution,
+    }))
+}
+
+// Only native balances exist, so the one path there can be is a direct native payment
+fn strict_receive_paths(
+    state: &LedgerState,
+    base_url: &str,
+    request: &Request,
+) -> Result<Response, Response> {
+    let amount = amount_param(request, "destination_amount")?;
+    let native_source = match request.params.get("source_account") {
+        Some(account_id) => state.accounts.contains_key(account_id),
+        None => has_native(request.params.get("source_assets")),
+    };
+    let native_destination = is_native(request, "destination");
+
+    paths(
+        base_url,
+        "/paths/strict-receive",
+        native_source && native_destination,
+        amount,
+    )
+}
+
+fn strict_send_paths(
+    state: &LedgerState,
+    base_url: &str,
+    request: &Request,
+) -> Result<Response, Response> {
+    let amount = amount_param(request, "source_amount")?;
+    let native_destination = match request.params.get("destination_account") {
+        Some(account_id) => state.accounts.contains_key(account_id),
+        None => has_native(request.params.get("destination_assets")),
+    };
+    let native_source = is_native(request, "source");
+
+    paths(
+        base_url,
+        "/paths/strict-send",
+        native_source && native_destination,
+        amount,
+    )
+}
+
+fn paths(base_url: &str, path: &str, found: bool, amount: Amount) -> Result<Response, Response> {
+    let records = match found {
+        true => vec![json!({
+            "source_asset_type": "native",
+            "source_amount": amount.to_string(),
+            "destination_asset_type": "native",
+            "destination_amount": amount.to_string(),
+            "path": [],
+        })],
+        false => Vec::new(),
+    };
+
+    Ok(Response::ok(json!({
+        "_links": { "self": { "href": format!("{}{}", base_url, path) } },
+        "_embedded": { "records": records },
+    })))
+}
+
+fn amount_param(request: &Request, name: &str) -> Result<Amount, Response> {
+    request
+        .params
+        .get(name)
+        .and_then(|amount| Amount::from_str(amount).ok())
+        .ok_or_else(|| bad_request(&format!("{} is invalid", name)))
+}
+
+fn is_native(request: &Request, prefix: &str) -> bool {
+    request
+        .params
+        .get(&format!("{}_asset_type", prefix))
+        .is_some_and(|asset_type| asset_type == "native")
+}
+
+fn has_native(assets: Option<&String>) -> bool {
+    assets.is_some_and(|assets| assets.split(',').any(|asset| asset.trim() == "native"))
+}
+
+// Nobody can hold other assets, so every order book is empty
+fn order_book(request: &Request) -> Response {
+    let asset = |prefix: &str| {
+        json!({
+            "asset_type": request.params.get(&format!("{}_asset_type", prefix)),
+            "asset_code": request.params.get(&format!("{}_asset_code", prefix)),
+            "asset_issuer": request.params.get(&format!("{}_asset_issuer", prefix)),
+        })
+    };
+
+    Response::ok(json!({
+        "bids": [],
+        "asks": [],
+        "base": asset("selling"),
+        "counter": asset("buying"),
+    }))
+}
+
+// Records are built from the typed models, so that they cannot drift from what
+// Server parses
+fn account(base_url: &str, id: &str, entry: &AccountEntry) -> Account {
+    let link = |path: &str| ResponseLink {
+        href: format!("{}/accounts/{}{}", base_url, id, path),
+        templated: None,
+    };
+
+    Account {
+        _links: AccountLinks {
+            itself: link(""),
+            transactions: link("/transactions"),
+            operations: link("/operations"),
+            payments: link("/payments"),
+            effects: link("/effects"),
+            offers: link("/offers"),
+            trades: link("/trades"),
+        },
+        id: id.to_string(),
+        account_id: id.to_string(),
+        sequence: entry.sequence.to_string(),
+        subentry_count: 0,
+        inflation_destination: None,
+        home_domain: None,
+        last_modified_ledger: u64::from(entry.last_modified_ledger),
+        last_modified_time: Some(entry.last_modified_time),
+        thresholds: AccountThresholds {
+            low_threshold: 0,
+            med_threshold: 0,
+            high_threshold: 0,
+        },
+        flags: Flags {
+            auth_required: false,
+            auth_revocable: false,
+            auth_immutable: false,
+            auth_clawback_enabled: false,
+        },
+        balances: vec![Balance {
+            balance: Amount::from_stroops(entry.balance),
+            limit: None,
+            asset_type: String::from("native"),
+            asset_code: None,
+            asset_issuer: None,
+            buying_liabilities: Some(Amount::from_stroops(0)),
+            selling_liabilities: Some(Amount::from_stroops(0)),
+            last_modified_ledger: None,
+            is_authorized: None,
+            is_authorized_to_maintain_liabilities: None,
+            is_clawback_enabled: None,
+            liquidity_pool_id: None,
+        }],
+        signers: vec![AccountSigner {
+            weight: 1,
+            key: id.to_string(),
+            r#type: String::from("ed25519_public_key"),
+            sponsor: None,
+        }],
+        data: HashMap::new(),
+        num_sponsoring: 0,
+        num_sponsored: 0,
+        paging_token: id.to_string(),
+    }
+}
+
+fn ledger(base_url: &str, entry: &LedgerEntry) -> Ledger {
+    let link = |path: &str| ResponseLink {
+        href: format!("{}/ledgers/{}{}", base_url, entry.sequence, path),
+        templated: None,
+    };
+
+    Ledger {
+        _links: LedgerLinks {
+            itself: link(""),
+            transactions: link("/transactions"),
+            operations: link("/operations"),
+            payments: link("/payments"),
+            effects: link("/effects"),
+        },
+        id: entry.hash.clone(),
+        paging_token: (i64::from(entry.sequence) << 32).to_string(),
+        hash: entry.hash.clone(),
+        prev_hash: entry.prev_hash.clone(),
+        sequence: entry.sequence,
+        successful_transaction_count: entry.successful_transaction_count,
+        failed_transaction_count: entry.failed_transaction_count,
+        operation_count: entry.operation_count,
+        tx_set_operation_count: entry.operation_count,
+        closed_at: entry.closed_at,
+        total_coins: String::from("100000000000.0000000"),
+        fee_pool: String::from("0.0000000"),
+        base_fee_in_stroops: BASE_FEE as u32,
+        base_reserve_in_stroops: BASE_RESERVE as u64,
+        max_tx_set_size: 1000,
+        protocol_version: 20,
+        header_xdr: String::new(),
+    }
+}
+
+// Also the body of a successful submission, which has the same fields
+fn transaction_json(base_url: &str, tx: &TransactionEntry) -> Value {
+    let href = |path: String| json!({ "href": format!("{}{}", base_url, path) });
+
+    json!({
+        "_links": {
+            "self": href(format!("/transactions/{}", tx.hash)),
+            "account": href(format!("/accounts/{}", tx.source_account)),
+            "ledger": href(format!("/ledgers/{}", tx.ledger)),
+            "operations": href(format!("/transactions/{}/operations", tx.hash)),
+            "effects": href(format!("/transactions/{}/effects", tx.hash)),
+            "precedes": href(format!("/transactions?order=asc&cursor={}", tx.toid)),
+            "succeeds": href(format!("/transactions?order=desc&cursor={}", tx.toid)),
+            "transaction": href(format!("/transactions/{}", tx.hash)),
+        },
+        "id": tx.hash,
+        "paging_token": tx.toid.to_string(),
+        "successful": tx.successful,
+        "hash": tx.hash,
+        "ledger": tx.ledger,
+        "created_at": timestamp(&tx.created_at),
+        "source_account": tx.source_account,
+        "source_account_sequence": tx.source_account_sequence.to_string(),
+        "fee_account": tx.source_account,
+        "fee_charged": tx.fee_charged.to_string(),
+        "max_fee": tx.max_fee.to_string(),
+        "operation_count": tx.operation_count,
+        "envelope_xdr": tx.envelope_xdr,
+        "result_xdr": tx.result_xdr,
+        "fee_meta_xdr": "",
+        "memo_type": tx.memo_type,
+        "memo": tx.memo,
+        "signatures": tx.signatures,
+        "valid_after": tx.valid_after.as_ref().map(timestamp),
+        "valid_before": tx.valid_before.as_ref().map(timestamp),
+    })
+}
+
+fn operation_json(base_url: &str, op: &OperationEntry) -> Value {
+    let href = |path: String| json!({ "href": format!("{}{}", base_url, path) });
+
+    let mut operation = json!({
+        "_links": {
+            "self": href(format!("/operations/{}", op.toid)),
+            "transaction": href(format!("/transactions/{}", op.transaction_hash)),
+            "effects": href(format!("/operations/{}/effects", op.toid)),
+            "succeeds": href(format!("/effects?order=desc&cursor={}", op.toid)),
+            "precedes": href(format!("/effects?order=asc&cursor={}", op.toid)),
+        },
+        "id": op.toid.to_string(),
+        "paging_token": op.toid.to_string(),
+        "transaction_successful": op.transaction_successful,
+        "source_account": op.source_account,
+        "created_at": timestamp(&op.created_at),
+        "transaction_hash": op.transaction_hash,
+    });
+
+    let fields = match &op.body {
+        OperationBody::CreateAccount {
+            account,
+            starting_balance,
+        } => json!({
+            "type": "create_account",
+            "type_i": 0,
+            "starting_balance": Amount::from_stroops(*starting_balance).to_string(),
+            "funder": op.source_account,
+            "account": account,
+        }),
+        OperationBody::Payment { to, amount } => json!({
+            "type": "payment",
+            "type_i": 1,
+            "asset_type": "native",
+            "from": op.source_account,
+            "to": to,
+            "amount": Amount::from_stroops(*amount).to_string(),
+        }),
+    };
+    if let (Some(operation), Value::Object(fields)) = (operation.as_object_mut(), fields) {
+        operation.extend(fields);
+    }
+
+    operation
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn problem(status: u16, kind: &str, title: &str, extras: Value) -> Response {
+    Response {
+        status,
+        body: json!({
+            "type": format!("https://stellar.org/horizon-errors/{}", kind),
+            "title": title,
+            "status": status,
+            "extras": extras,
+        }),
+    }
+}
+
+fn not_found() -> Response {
+    let mut response = problem(404, "not_found", "Resource Missing", Value::Null);
+    response.body["detail"] = Value::from(
+        "The resource at the url requested was not found. This usually occurs for one of \
+         two reasons: The url requested is not valid, or no data in our database could \
+         be found with the parameters provided.",
+    );
+
+    response
+}
+
+fn bad_request(detail: &str) -> Response {
+    let mut response = problem(400, "bad_request", "Bad Request", Value::Null);
+    response.body["detail"] = Value::from(detail);
+
+    response
+}
+
+fn transaction_failed(
+    envelope_xdr: &str,
+    transaction: &str,
+    operations: Option<Vec<&str>>,
+    result_xdr: &str,
+) -> Response {
+    problem(
+        400,
+        "transaction_failed",
+        "Transaction Failed",
+        json!({
+            "envelope_xdr": envelope_xdr,
+            "result_codes": { "transaction": transaction, "operations": operations },
+            "result_xdr": result_xdr,
+        }),
+    )
+}
@@ -0,0 +1,156 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::endpoints::ServerOptions;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport};
+use crate::types::HttpMethod;
+use crate::Error;
+
+pub(crate) type ResponseFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, Error>> + Send + 'a>>;
+
+/// The async counterpart of [`HttpTransport`], which every call of `AsyncServer` goes
+/// through.
+pub(crate) trait AsyncHttpTransport: Debug + Send + Sync {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> ResponseFuture<'a>;
+}
+
+/// Builds the transport of `AsyncServer`: the transport set in `options`, cassette
+/// included, when there is one and a `reqwest` client otherwise.
+pub(crate) fn async_transport(
+    options: &ServerOptions,
+    transport: &Arc<dyn HttpTransport>,
+) -> Result<Arc<dyn AsyncHttpTransport>, Error> {
+    if options.transport.is_some() || options.cassette.is_some() {
+        return Ok(Arc::new(BlockingTransport(transport.clone())));
+    }
+
+    Ok(Arc::new(ReqwestTransport::from_options(options)?))
+}
+
+// Runs a blocking transport on the blocking thread pool of tokio
+#[derive(Debug)]
+struct BlockingTransport(Arc<dyn HttpTransport>);
+
+impl AsyncHttpTransport for BlockingTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> ResponseFuture<'a> {
+        let transport = self.0.clone();
+        let request = request.clone();
+
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || transport.send(&request))
+                .await
+                .map_err(|e| Error::Transport(Box::new(e)))?
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    // The client follows the timeouts, proxy and root certificates of options
+    fn from_options(options: &ServerOptions) -> Result<Self, Error> {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &options.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| Error::InvalidUrl(format!("invalid proxy: {}", e)))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(pem) = &options.root_certificates {
+            let certificates = reqwest::Certificate::from_pem_bundle(pem)
+                .map_err(|e| Error::InvalidCertificate(e.to_string()))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        Ok(Self {
+            client: builder.build().map_err(reqwest_error)?,
+        })
+    }
+
+    fn build(&self, req: &HttpRequest) -> reqwest::RequestBuilder {
+        let mut builder = match req.method {
+            HttpMethod::GET => self.client.get(&req.url),
+            HttpMethod::POST => self.client.post(&req.url),
+        };
+        for (name, value) in req.headers.iter() {
+            builder = builder.header(name, value);
+        }
+
+        builder.query(&req.query_params)
+    }
+}
+
+impl AsyncHttpTransport for ReqwestTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> ResponseFuture<'a> {
+        let req = self.build(request);
+
+        Box::pin(async move {
+            let res = req.send().await.map_err(reqwest_error)?;
+            let status = res.status();
+            let headers = res
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+                })
+                .collect::<Vec<(String, String)>>();
+            let res_str = res.text().await.map_err(reqwest_error)?;
+
+            Ok(headers.iter().fold(
+                HttpResponse::new(status.as_u16(), &res_str),
+                |res, (name, value)| res.with_header(name, value),
+            ))
+        })
+    }
+}
+
+fn reqwest_error(e: reqwest::Error) -> Error {
+    if e.is_timeout() {
+        return Error::Timeout;
+    }
+
+    Error::Transport(Box::new(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+
+    #[tokio::test]
+    async fn test_async_transport_goes_through_options_transport() {
+        let mock = Arc::new(MockTransport::new());
+        mock.add_json("https://horizon.stellar.org/ledgers/1", "{}");
+        let options = ServerOptions {
+            transport: Some(mock.clone()),
+            ..Default::default()
+        };
+        let transport: Arc<dyn HttpTransport> = mock.clone();
+
+        let res = async_transport(&options, &transport)
+            .unwrap()
+            .send(&HttpRequest::new(
+                HttpMethod::GET,
+                "https://horizon.stellar.org/ledgers/1",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(200, res.status);
+        assert_eq!(1, mock.requests().len());
+    }
+}
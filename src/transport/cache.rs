@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use crate::transport::{HttpRequest, HttpResponse};
+
+/// A response kept by a [`ResponseCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub response: HttpResponse,
+    /// When the response has to be revalidated, `None` for resources that never change.
+    pub expires_at: Option<SystemTime>,
+}
+
+impl CachedResponse {
+    fn is_fresh(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => SystemTime::now() < expires_at,
+            None => true,
+        }
+    }
+}
+
+/// Where a [`ResponseCache`] keeps its responses, e.g. in memory with [`LruCache`] or
+/// in a store shared between processes.
+pub trait CacheBackend: Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+    fn put(&self, key: &str, response: CachedResponse);
+    fn remove(&self, key: &str);
+}
+
+/// An in-memory [`CacheBackend`] that evicts the least recently used response once it
+/// holds `capacity` of them.
+#[derive(Debug)]
+pub struct LruCache {
+    capacity: usize,
+    entries: Mutex<LruEntries>,
+}
+
+#[derive(Debug, Default)]
+struct LruEntries {
+    // Each response with the tick of its last use
+    responses: HashMap<String, (CachedResponse, u64)>,
+    tick: u64,
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(LruEntries::default()),
+        }
+    }
+}
+
+impl CacheBackend for LruCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.tick += 1;
+        let tick = entries.tick;
+
+        let (response, last_used) = entries.responses.get_mut(key)?;
+        *last_used = tick;
+
+        Some(response.clone())
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.tick += 1;
+        let tick = entries.tick;
+
+        if !entries.responses.contains_key(key) && entries.responses.len() >= self.capacity {
+            let oldest = entries
+                .responses
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.responses.remove(&oldest);
+            }
+        }
+        entries
+            .responses
+            .insert(String::from(key), (response, tick));
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().responses.remove(key);
+    }
+}
+
+/// How often a [`ResponseCache`] could answer without Horizon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Answered from the cache without a request.
+    pub hits: u64,
+    /// Answered from the cache after Horizon confirmed it with a 304.
+    pub revalidations: u64,
+    /// Fetched from Horizon.
+    pub misses: u64,
+}
+
+/// Caches the GET responses of a [`Server`](crate::Server).
+///
+/// Ledgers, transactions and operations loaded by ID never change and are kept until
+/// the backend evicts them. Other responses follow their `Cache-Control` header, and
+/// are revalidated with `If-None-Match` once stale when Horizon sent an `ETag`.
+///
+/// ```
+/// use std::sync::Arc;
+/// use stellar_sdk::{transport::ResponseCache, Server, ServerOptions};
+///
+/// let cache = Arc::new(ResponseCache::lru(10_000));
+/// let options = ServerOptions {
+///     cache: Some(cache.clone()),
+///     ..Default::default()
+/// };
+/// let s = Server::new(String::from("https://horizon.stellar.org"), Some(options)).unwrap();
+///
+/// assert_eq!(0, cache.stats().hits);
+/// ```
+#[derive(Debug)]
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+    hits: AtomicU64,
+    revalidations: AtomicU64,
+    misses: AtomicU64,
+}
+
+pub(crate) enum CacheLookup {
+    Fresh(HttpResponse),
+    // A stale response that Horizon can confirm with a 304
+    Stale(String),
+    Miss,
+}
+
+impl ResponseCache {
+    pub fn new(backend: Arc<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
+            hits: AtomicU64::new(0),
+            revalidations: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// A cache keeping up to `capacity` responses in memory.
+    pub fn lru(capacity: usize) -> Self {
+        Self::new(Arc::new(LruCache::new(capacity)))
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            revalidations: self.revalidations.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn lookup(&self, request: &HttpRequest) -> CacheLookup {
+        let cached = match self.backend.get(&cache_key(request)) {
+            Some(cached) => cached,
+            None => return CacheLookup::Miss,
+        };
+
+        if cached.is_fresh() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return CacheLookup::Fresh(cached.response);
+        }
+
+        match cached.response.header("ETag") {
+            Some(etag) => CacheLookup::Stale(String::from(etag)),
+            None => CacheLookup::Miss,
+        }
+    }
+
+    /// Stores the response when it may be reused, and swaps a 304 for the cached
+    /// response it confirms.
+    pub(crate) fn store(
+        &self,
+        request: &HttpRequest,
+        response: HttpResponse,
+        immutable: bool,
+    ) -> HttpResponse {
+        let key = cache_key(request);
+
+        if response.status == 304 {
+            if let Some(mut cached) = self.backend.get(&key) {
+                self.revalidations.fetch_add(1, Ordering::Relaxed);
+                cached.expires_at = Some(expires_at(&response).unwrap_or_else(SystemTime::now));
+                self.backend.put(&key, cached.clone());
+
+                return cached.response;
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        if !response.is_success() {
+            return response;
+        }
+
+        let expires_at = if immutable {
+            None
+        } else {
+            let cache_control = response.header("Cache-Control").unwrap_or_default();
+            let storable = !cache_control.contains("no-store")
+                && (cache_control.contains("max-age") || response.header("ETag").is_some());
+            if !storable {
+                self.backend.remove(&key);
+                return response;
+            }
+
+            Some(expires_at(&response).unwrap_or_else(SystemTime::now))
+        };
+
+        self.backend.put(
+            &key,
+            CachedResponse {
+                response: response.clone(),
+                expires_at,
+            },
+        );
+
+        response
+    }
+}
+
+fn cache_key(request: &HttpRequest) -> String {
+    let query = request
+        .query_params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>()
+        .join("&");
+
+    format!("{}?{}", request.url, query)
+}
+
+// When a response stops being fresh according to its Cache-Control max-age
+fn expires_at(response: &HttpResponse) -> Option<SystemTime> {
+    let cache_control = response.header("Cache-Control")?;
+    if cache_control.contains("no-cache") {
+        return Some(SystemTime::now());
+    }
+
+    let max_age = cache_control
+        .split(',')
+        .find_map(|directive| directive.trim().strip_prefix("max-age="))?
+        .parse::<u64>()
+        .ok()?;
+
+    Some(SystemTime::now() + Duration::from_secs(max_age))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::HttpMethod;
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let cache = LruCache::new(2);
+        let response = CachedResponse {
+            response: HttpResponse::new(200, "{}"),
+            expires_at: None,
+        };
+
+        cache.put("a", response.clone());
+        cache.put("b", response.clone());
+        cache.get("a");
+        cache.put("c", response);
+
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn test_response_cache_follows_cache_control() {
+        let cache = ResponseCache::lru(10);
+        let request = HttpRequest::new(HttpMethod::GET, "https://horizon.stellar.org/fee_stats");
+
+        let response = HttpResponse::new(200, "{}").with_header("Cache-Control", "no-store");
+        cache.store(&request, response, false);
+        assert!(matches!(cache.lookup(&request), CacheLookup::Miss));
+
+        let response = HttpResponse::new(200, "{}").with_header("Cache-Control", "max-age=60");
+        cache.store(&request, response, false);
+        assert!(matches!(cache.lookup(&request), CacheLookup::Fresh(_)));
+
+        let response = HttpResponse::new(200, "{}")
+            .with_header("Cache-Control", "no-cache")
+            .with_header("ETag", "\"v1\"");
+        cache.store(&request, response, false);
+        assert!(matches!(cache.lookup(&request), CacheLookup::Stale(etag) if etag == "\"v1\""));
+
+        assert_eq!(
+            CacheStats {
+                hits: 1,
+                revalidations: 0,
+                misses: 3,
+            },
+            cache.stats()
+        );
+    }
+
+    #[test]
+    fn test_response_cache_revalidates_with_etag() {
+        let cache = ResponseCache::lru(10);
+        let request = HttpRequest::new(HttpMethod::GET, "https://horizon.stellar.org/accounts/GA");
+
+        let response = HttpResponse::new(200, "{\"sequence\": \"1\"}")
+            .with_header("Cache-Control", "max-age=0")
+            .with_header("ETag", "\"v1\"");
+        cache.store(&request, response.clone(), false);
+
+        let not_modified = HttpResponse::new(304, "").with_header("Cache-Control", "max-age=60");
+        assert_eq!(response, cache.store(&request, not_modified, false));
+        assert!(matches!(cache.lookup(&request), CacheLookup::Fresh(_)));
+        assert_eq!(1, cache.stats().revalidations);
+    }
+}
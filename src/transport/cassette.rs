@@ -0,0 +1,269 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::transport::{split_url, HttpRequest, HttpResponse, HttpTransport};
+use crate::types::HttpMethod;
+use crate::Error;
+
+/// Whether a [`Server`](crate::Server) records its requests to a cassette file or
+/// answers them from one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Sends every request and writes it to the file along with its response.
+    Record(PathBuf),
+    /// Serves the responses of the file and fails on requests it does not hold.
+    Replay(PathBuf),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: HttpMethod,
+    url: String,
+    query_params: BTreeMap<String, String>,
+}
+
+impl RecordedRequest {
+    // The query string of links such as `_links.next.href` is folded into the params,
+    // so that the same request matches however it was built
+    fn new(request: &HttpRequest) -> Self {
+        let (url, mut query_params) = split_url(&request.url);
+        query_params.extend(request.query_params.clone());
+
+        Self {
+            method: request.method,
+            url,
+            query_params,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+/// An [`HttpTransport`] that records requests and responses to a JSON cassette file,
+/// or replays them from one, so that tests run offline and give the same results
+/// every time.
+///
+/// Identical requests are replayed in the order they were recorded; once their
+/// responses are used up the last one is served again. Request headers are not
+/// recorded and play no part in matching. The calls of `AsyncServer` go through the
+/// cassette too.
+///
+/// ```no_run
+/// use stellar_sdk::{transport::CassetteMode, Server, ServerOptions};
+///
+/// let options = ServerOptions {
+///     cassette: Some(CassetteMode::Replay("tests/cassettes/load_ledger.json".into())),
+///     ..Default::default()
+/// };
+/// let s = Server::new(String::from("https://horizon.stellar.org"), Some(options)).unwrap();
+///
+/// let ledger = s.load_ledger(1).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct CassetteTransport {
+    path: PathBuf,
+    // Sends the requests while recording, None while replaying
+    inner: Option<Arc<dyn HttpTransport>>,
+    cassette: Mutex<Cassette>,
+    // The interactions already replayed
+    replayed: Mutex<HashSet<usize>>,
+}
+
+impl CassetteTransport {
+    /// Sends requests through `inner`, writing the cassette after each response.
+    pub fn record(path: impl AsRef<Path>, inner: Arc<dyn HttpTransport>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            inner: Some(inner),
+            cassette: Mutex::new(Cassette::default()),
+            replayed: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Loads a cassette written in record mode.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let cassette = serde_json::from_str::<Cassette>(&fs::read_to_string(&path)?)?;
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            inner: None,
+            cassette: Mutex::new(cassette),
+            replayed: Mutex::new(HashSet::new()),
+        })
+    }
+
+    pub(crate) fn from_mode(
+        mode: &CassetteMode,
+        inner: Arc<dyn HttpTransport>,
+    ) -> Result<Self, Error> {
+        match mode {
+            CassetteMode::Record(path) => Ok(Self::record(path, inner)),
+            CassetteMode::Replay(path) => Self::replay(path),
+        }
+    }
+
+    fn write(&self, request: &HttpRequest, response: &HttpResponse) -> Result<(), Error> {
+        let mut cassette = self.cassette.lock().unwrap();
+        cassette.interactions.push(Interaction {
+            request: RecordedRequest::new(request),
+            response: RecordedResponse {
+                status: response.status,
+                headers: response.headers.clone().into_iter().collect(),
+                body: response.body.clone(),
+            },
+        });
+
+        fs::write(&self.path, serde_json::to_string_pretty(&*cassette)?)?;
+
+        Ok(())
+    }
+
+    fn play(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        let recorded = RecordedRequest::new(request);
+        let cassette = self.cassette.lock().unwrap();
+        let mut replayed = self.replayed.lock().unwrap();
+
+        let matches = cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.request == recorded)
+            .collect::<Vec<_>>();
+        let (index, interaction) = matches
+            .iter()
+            .find(|(index, _)| !replayed.contains(index))
+            .or_else(|| matches.last())
+            .ok_or_else(|| {
+                Error::UnmatchedRequest(format!(
+                    "{:?} {} is not in {}",
+                    request.method,
+                    request.url,
+                    self.path.display()
+                ))
+            })?;
+        replayed.insert(*index);
+
+        let response = &interaction.response;
+        Ok(response.headers.iter().fold(
+            HttpResponse::new(response.status, &response.body),
+            |res, (name, value)| res.with_header(name, value),
+        ))
+    }
+}
+
+impl HttpTransport for CassetteTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return self.play(request),
+        };
+
+        let response = inner.send(request)?;
+        self.write(request, &response)?;
+
+        Ok(response)
+    }
+
+    // Streams never end, so they are passed through while recording
+    fn open_stream(&self, request: &HttpRequest) -> Result<Box<dyn BufRead + Send>, Error> {
+        match &self.inner {
+            Some(inner) => inner.open_stream(request),
+            None => {
+                let response = self.play(request)?;
+                if !response.is_success() {
+                    return Err(Error::from_response(&request.url, response));
+                }
+
+                Ok(Box::new(std::io::Cursor::new(response.body.into_bytes())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::transport::MockTransport;
+
+    #[test]
+    fn test_record_and_replay() {
+        let path = std::env::temp_dir().join(format!("cassette-{}.json", std::process::id()));
+        let mock = Arc::new(MockTransport::new());
+        mock.add_response(
+            "https://horizon.stellar.org/ledgers",
+            HttpResponse::new(200, "{}").with_header("Date", "Tue, 15 Nov 1994 08:12:31 GMT"),
+        );
+        let request = HttpRequest::new(
+            HttpMethod::GET,
+            "https://horizon.stellar.org/ledgers?limit=2",
+        );
+
+        let recorder = CassetteTransport::record(&path, mock.clone());
+        recorder.send(&request).unwrap();
+
+        let player = CassetteTransport::replay(&path).unwrap();
+        let same_request = HttpRequest::new(HttpMethod::GET, "https://horizon.stellar.org/ledgers")
+            .with_query_params(&HashMap::from([(String::from("limit"), String::from("2"))]));
+        let response = player.send(&same_request).unwrap();
+
+        assert_eq!("{}", response.body);
+        assert_eq!(
+            Some("Tue, 15 Nov 1994 08:12:31 GMT"),
+            response.header("Date")
+        );
+        assert!(player.send(&same_request).is_ok());
+        assert!(matches!(
+            player.send(&HttpRequest::new(
+                HttpMethod::GET,
+                "https://horizon.stellar.org/ledgers"
+            )),
+            Err(Error::UnmatchedRequest(_))
+        ));
+        assert_eq!(1, mock.requests().len());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_server_replay_fails_on_unmatched_request() {
+        let path = std::env::temp_dir().join(format!("cassette-async-{}.json", std::process::id()));
+        fs::write(&path, r#"{"interactions": []}"#).unwrap();
+        let options = crate::ServerOptions {
+            cassette: Some(CassetteMode::Replay(path.clone())),
+            ..Default::default()
+        };
+
+        let s = crate::AsyncServer::new(String::from("https://horizon.stellar.org"), Some(options))
+            .unwrap();
+        let res = s
+            .load_account("GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM")
+            .await;
+
+        assert!(matches!(res, Err(Error::UnmatchedRequest(_))));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::time::Duration;
+
+use crate::transport::HttpResponse;
+use crate::types::{HorizonError, HttpMethod};
+use crate::Error;
+
+/// Receives the outcome of every call a [`Server`](crate::Server) makes to Horizon,
+/// e.g. to count requests and errors per endpoint or to build latency histograms.
+///
+/// `record` is called from the thread making the call, after the response was
+/// decoded, so implementations should be cheap and must not block.
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::sync::Arc;
+/// use stellar_sdk::transport::{MetricsSink, RequestMetrics};
+/// use stellar_sdk::{Server, ServerOptions};
+///
+/// #[derive(Debug, Default)]
+/// struct ErrorCounter(AtomicU64);
+///
+/// impl MetricsSink for ErrorCounter {
+///     fn record(&self, request: &RequestMetrics) {
+///         if request.error.is_some() {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let options = ServerOptions {
+///     metrics: Some(Arc::new(ErrorCounter::default())),
+///     ..Default::default()
+/// };
+/// let s = Server::new(String::from("https://horizon.stellar.org"), Some(options)).unwrap();
+/// ```
+pub trait MetricsSink: Debug + Send + Sync {
+    fn record(&self, request: &RequestMetrics);
+}
+
+/// One call to Horizon, retries and cache hits included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetrics {
+    pub method: HttpMethod,
+    /// The path of the endpoint with its ids left out, e.g. `/accounts/{id}/payments`,
+    /// so that it can be used as a metric label.
+    pub endpoint: String,
+    pub latency: Duration,
+    /// `None` when the call succeeded.
+    pub error: Option<ErrorClass>,
+}
+
+/// What a failed call to Horizon ran into, coarse enough to be used as a metric label.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ErrorClass {
+    /// Horizon answered with a problem document of this type, e.g. `not_found` or
+    /// `transaction_failed`.
+    Problem(String),
+    /// A non 2xx response whose body is not a problem document.
+    Status(u16),
+    /// The request never got a response, timeouts included.
+    Transport,
+    /// The response body could not be decoded.
+    Decode,
+    Other,
+}
+
+impl ErrorClass {
+    // The class of a non 2xx response handed back as is by send_request
+    pub(crate) fn from_response(res: &HttpResponse) -> Self {
+        match serde_json::from_str::<HorizonError>(&res.body) {
+            Ok(problem) => ErrorClass::problem(&problem),
+            Err(_) => ErrorClass::Status(res.status),
+        }
+    }
+
+    // Horizon types its problems with a URL, e.g. https://stellar.org/horizon-errors/not_found
+    fn problem(problem: &HorizonError) -> Self {
+        let problem_type = problem.r#type.rsplit('/').next().unwrap_or_default();
+
+        ErrorClass::Problem(String::from(problem_type))
+    }
+}
+
+impl From<&Error> for ErrorClass {
+    fn from(e: &Error) -> Self {
+        match e {
+            Error::Horizon(problem)
+            | Error::NotFound(Some(problem))
+            | Error::RateLimited {
+                problem: Some(problem),
+                ..
+            } => ErrorClass::problem(problem),
+            Error::NotFound(None) => ErrorClass::Status(404),
+            Error::RateLimited { problem: None, .. } => ErrorClass::Status(429),
+            Error::Status { status, .. } => ErrorClass::Status(*status),
+            Error::Transport(_) | Error::Timeout | Error::UnmatchedRequest(_) => {
+                ErrorClass::Transport
+            }
+            Error::Decode(_) => ErrorClass::Decode,
+            _ => ErrorClass::Other,
+        }
+    }
+}
+
+impl Display for ErrorClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorClass::Problem(problem_type) => write!(f, "{}", problem_type),
+            ErrorClass::Status(status) => write!(f, "http_{}", status),
+            ErrorClass::Transport => write!(f, "transport"),
+            ErrorClass::Decode => write!(f, "decode"),
+            ErrorClass::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// The path of the url with every id replaced by `{id}`, and data entry names by `{key}`.
+///
+/// Resource names are lowercase words, anything else in the path (account ids,
+/// hashes, sequences, ...) is an id.
+pub(crate) fn endpoint(url: &str) -> String {
+    let path = match url::Url::parse(url) {
+        Ok(url) => String::from(url.path()),
+        Err(_) => String::from(url.split('?').next().unwrap_or_default()),
+    };
+
+    let mut endpoint = String::new();
+    let mut previous = "";
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        let is_name = segment
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c == '_' || c == '-');
+
+        endpoint.push('/');
+        endpoint.push_str(match (previous, is_name) {
+            ("data", _) => "{key}",
+            (_, true) => segment,
+            (_, false) => "{id}",
+        });
+        previous = segment;
+    }
+
+    match endpoint.is_empty() {
+        true => String::from("/"),
+        false => endpoint,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::api_call::api_call;
+    use crate::transport::MockTransport;
+    use crate::{Server, ServerOptions};
+
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<RequestMetrics>>);
+
+    impl MetricsSink for Recorder {
+        fn record(&self, request: &RequestMetrics) {
+            self.0.lock().unwrap().push(request.clone());
+        }
+    }
+
+    #[test]
+    fn test_endpoint_leaves_out_ids() {
+        assert_eq!("/", endpoint("https://horizon.stellar.org"));
+        assert_eq!(
+            "/accounts/{id}/payments",
+            endpoint("https://horizon.stellar.org/accounts/GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7/payments?limit=2")
+        );
+        assert_eq!(
+            "/accounts/{id}/data/{key}",
+            endpoint("https://horizon.stellar.org/accounts/GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7/data/config.memo_required")
+        );
+        assert_eq!(
+            "/horizon/ledgers/{id}/transactions",
+            endpoint("https://gateway.example.com/horizon/ledgers/43000000/transactions")
+        );
+        assert_eq!(
+            "/paths/strict-receive",
+            endpoint("https://horizon.stellar.org/paths/strict-receive")
+        );
+    }
+
+    #[test]
+    fn test_error_class() {
+        let not_found = r#"{"type":"https://stellar.org/horizon-errors/not_found","title":"Resource Missing","status":404}"#;
+
+        assert_eq!(
+            ErrorClass::Problem(String::from("not_found")),
+            ErrorClass::from_response(&HttpResponse::new(404, not_found))
+        );
+        assert_eq!(
+            ErrorClass::Status(502),
+            ErrorClass::from_response(&HttpResponse::new(502, "Bad Gateway"))
+        );
+        assert_eq!(ErrorClass::Transport, ErrorClass::from(&Error::Timeout));
+        assert_eq!(
+            ErrorClass::Decode,
+            ErrorClass::from(&Error::from(serde_json::from_str::<u32>("{").unwrap_err()))
+        );
+        assert_eq!(
+            "not_found",
+            ErrorClass::Problem(String::from("not_found")).to_string()
+        );
+        assert_eq!("http_429", ErrorClass::Status(429).to_string());
+    }
+
+    #[test]
+    fn test_server_records_every_call() {
+        let mock = Arc::new(MockTransport::new());
+        mock.add_json("https://horizon.stellar.org/ledgers/42", "{}");
+        mock.add_response(
+            "https://horizon.stellar.org/ledgers/43",
+            HttpResponse::new(
+                404,
+                r#"{"type":"https://stellar.org/horizon-errors/not_found","title":"Resource Missing","status":404}"#,
+            ),
+        );
+        let recorder = Arc::new(Recorder::default());
+        let options = ServerOptions {
+            transport: Some(mock),
+            metrics: Some(recorder.clone()),
+            ..Default::default()
+        };
+        let server =
+            Server::new(String::from("https://horizon.stellar.org"), Some(options)).unwrap();
+
+        for ledger in ["42", "43"] {
+            let url = format!("https://horizon.stellar.org/ledgers/{}", ledger);
+            let _ = api_call::<serde_json::Value>(url, HttpMethod::GET, &HashMap::new(), &server);
+        }
+
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(2, recorded.len());
+        assert!(recorded.iter().all(|r| r.endpoint == "/ledgers/{id}"));
+        assert_eq!(None, recorded[0].error);
+        assert_eq!(
+            Some(ErrorClass::Problem(String::from("not_found"))),
+            recorded[1].error
+        );
+    }
+}
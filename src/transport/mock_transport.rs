@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+#[cfg(test)]
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::transport::{split_url, HttpRequest, HttpResponse, HttpTransport};
+use crate::Error;
+#[cfg(test)]
+use crate::{Server, ServerOptions};
+
+#[derive(Debug)]
+struct MockRoute {
+    url: String,
+    query_params: BTreeMap<String, String>,
+    response: HttpResponse,
+}
+
+/// An in-memory [`HttpTransport`] serving canned responses, for tests that must not
+/// touch the network.
+///
+/// Responses are matched on the URL without its query string, plus every query
+/// param given when the response was added. When several responses match, the one
+/// with the most query params wins, and later additions win ties. Every request
+/// is recorded and can be inspected with [`MockTransport::requests`].
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Mutex<Vec<MockRoute>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_response(&self, url: &str, response: HttpResponse) -> &Self {
+        self.add_response_for_query(url, &[], response)
+    }
+
+    pub fn add_response_for_query(
+        &self,
+        url: &str,
+        query_params: &[(&str, &str)],
+        response: HttpResponse,
+    ) -> &Self {
+        let (url, mut route_params) = split_url(url);
+        route_params.extend(
+            query_params
+                .iter()
+                .map(|(name, value)| (String::from(*name), String::from(*value))),
+        );
+
+        self.routes.lock().unwrap().push(MockRoute {
+            url,
+            query_params: route_params,
+            response,
+        });
+
+        self
+    }
+
+    pub fn add_json(&self, url: &str, body: &str) -> &Self {
+        self.add_response(
+            url,
+            HttpResponse::new(200, body).with_header("Content-Type", "application/json"),
+        )
+    }
+
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// A server on `https://horizon.stellar.org` answering every call from `mock`.
+#[cfg(test)]
+pub(crate) fn mock_server(mock: &Arc<MockTransport>) -> Server {
+    let options = ServerOptions {
+        transport: Some(mock.clone()),
+        ..Default::default()
+    };
+
+    Server::new(String::from("https://horizon.stellar.org"), Some(options))
+        .expect("Cannot connect to insecure horizon server")
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let (url, mut query_params) = split_url(&request.url);
+        query_params.extend(request.query_params.clone());
+
+        let routes = self.routes.lock().unwrap();
+        // max_by_key keeps the last of equal routes, i.e. the latest addition
+        routes
+            .iter()
+            .filter(|route| route.url == url)
+            .filter(|route| {
+                route
+                    .query_params
+                    .iter()
+                    .all(|(name, value)| query_params.get(name) == Some(value))
+            })
+            .max_by_key(|route| route.query_params.len())
+            .map(|route| route.response.clone())
+            .ok_or_else(|| {
+                Error::Transport(
+                    format!(
+                        "MockTransport has no response for {:?} {}",
+                        request.method, request.url
+                    )
+                    .into(),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::HttpMethod;
+
+    #[test]
+    fn test_most_specific_route_wins() {
+        let mock = MockTransport::new();
+        mock.add_json("https://horizon.stellar.org/ledgers", "\"any\"")
+            .add_response_for_query(
+                "https://horizon.stellar.org/ledgers",
+                &[("cursor", "10")],
+                HttpResponse::new(200, "\"cursor\""),
+            );
+
+        let first = HttpRequest::new(HttpMethod::GET, "https://horizon.stellar.org/ledgers");
+        let next = HttpRequest::new(
+            HttpMethod::GET,
+            "https://horizon.stellar.org/ledgers?cursor=10&limit=2",
+        );
+
+        assert_eq!("\"any\"", mock.send(&first).unwrap().body);
+        assert_eq!("\"cursor\"", mock.send(&next).unwrap().body);
+        assert_eq!(2, mock.requests().len());
+    }
+
+    #[test]
+    fn test_later_route_wins_ties() {
+        let mock = MockTransport::new();
+        mock.add_response_for_query(
+            "https://horizon.stellar.org/ledgers",
+            &[("limit", "2")],
+            HttpResponse::new(200, "\"first page\""),
+        )
+        .add_json(
+            "https://horizon.stellar.org/ledgers?cursor=10",
+            "\"next page\"",
+        );
+
+        let next = HttpRequest::new(
+            HttpMethod::GET,
+            "https://horizon.stellar.org/ledgers?cursor=10&limit=2",
+        );
+
+        assert_eq!("\"next page\"", mock.send(&next).unwrap().body);
+    }
+
+    #[test]
+    fn test_unmatched_request_fails() {
+        let mock = MockTransport::new();
+        mock.add_json("https://horizon.stellar.org/ledgers", "{}");
+
+        let request = HttpRequest::new(HttpMethod::GET, "https://horizon.stellar.org/accounts");
+
+        assert!(mock.send(&request).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(feature = "async")]
+mod async_transport;
+mod cache;
+mod cassette;
+mod metrics;
+mod mock_transport;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod rate_limit;
+mod retry_policy;
+mod trace;
+mod ureq_transport;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::io::{BufRead, Cursor};
+use url::Url;
+
+use crate::types::HttpMethod;
+
+use crate::Error;
+#[cfg(feature = "async")]
+pub(crate) use async_transport::{async_transport, AsyncHttpTransport};
+pub(crate) use cache::CacheLookup;
+pub use cache::{CacheBackend, CacheStats, CachedResponse, LruCache, ResponseCache};
+pub use cassette::{CassetteMode, CassetteTransport};
+pub(crate) use metrics::endpoint;
+pub use metrics::{ErrorClass, MetricsSink, RequestMetrics};
+#[cfg(test)]
+pub(crate) use mock_transport::mock_server;
+pub use mock_transport::MockTransport;
+#[cfg(feature = "prometheus")]
+pub use prometheus::PrometheusMetrics;
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use retry_policy::RetryPolicy;
+pub(crate) use retry_policy::NO_RETRY_POLICY;
+pub(crate) use trace::{stream_reconnect, RequestSpan};
+pub use ureq_transport::UreqTransport;
+
+// Sent as X-Client-Name and X-Client-Version so that Horizon operators can tell
+// which SDK a request comes from
+pub(crate) const CLIENT_NAME: &str = "rust-stellar-sdk";
+pub(crate) const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Splits any query string off the url so that links such as `_links.next.href` match
+// requests built from a url and separate query params
+pub(crate) fn split_url(url: &str) -> (String, BTreeMap<String, String>) {
+    match Url::parse(url) {
+        Ok(mut parsed) => {
+            let query_params = parsed.query_pairs().into_owned().collect();
+            parsed.set_query(None);
+            (String::from(parsed.as_str()), query_params)
+        }
+        Err(_) => (String::from(url), BTreeMap::new()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub query_params: BTreeMap<String, String>,
+    pub headers: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    pub fn new(method: HttpMethod, url: &str) -> Self {
+        Self {
+            method,
+            url: String::from(url),
+            query_params: BTreeMap::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn with_query_params(mut self, query_params: &HashMap<String, String>) -> Self {
+        self.query_params.extend(query_params.clone());
+
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+
+        self
+    }
+
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers.extend(headers);
+
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    // Header names are stored lowercased
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: HashMap::new(),
+            body: String::from(body),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .insert(name.to_ascii_lowercase(), String::from(value));
+
+        self
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends the HTTP requests issued by [`Server`](crate::Server), its call builders and the
+/// [`StellarTomlResolver`](crate::StellarTomlResolver).
+///
+/// Non 2xx responses must be returned as `Ok`; `Err` is reserved for requests that
+/// never got a response, such as DNS or connection failures.
+pub trait HttpTransport: Debug + Send + Sync {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error>;
+
+    /// Opens a Server-Sent Events response. The default implementation buffers the
+    /// whole body, which only suits transports serving finite canned responses.
+    fn open_stream(&self, request: &HttpRequest) -> Result<Box<dyn BufRead + Send>, Error> {
+        let res = self.send(request)?;
+
+        if !res.is_success() {
+            return Err(Error::from_response(&request.url, res));
+        }
+
+        Ok(Box::new(Cursor::new(res.body.into_bytes())))
+    }
+}
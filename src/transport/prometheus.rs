@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+use crate::transport::{MetricsSink, RequestMetrics};
+
+// The upper bounds, in seconds, of the latency histogram buckets
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// A [`MetricsSink`] keeping request counters, error counters and latency histograms
+/// per method and endpoint, rendered in the Prometheus text exposition format.
+///
+/// The same sink can be shared by several servers and served from a `/metrics`
+/// endpoint of the application:
+///
+/// ```
+/// use std::sync::Arc;
+/// use stellar_sdk::{transport::PrometheusMetrics, Server, ServerOptions};
+///
+/// let metrics = Arc::new(PrometheusMetrics::new());
+/// let options = ServerOptions {
+///     metrics: Some(metrics.clone()),
+///     ..Default::default()
+/// };
+/// let s = Server::new(String::from("https://horizon.stellar.org"), Some(options)).unwrap();
+///
+/// // In the handler of GET /metrics
+/// let body = metrics.render();
+/// ```
+#[derive(Debug, Default)]
+pub struct PrometheusMetrics {
+    // Keyed by method and endpoint
+    series: Mutex<BTreeMap<(String, String), Series>>,
+}
+
+#[derive(Debug, Default)]
+struct Series {
+    requests: u64,
+    latency_sum: f64,
+    // Requests per latency bucket, not cumulative
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    errors: BTreeMap<String, u64>,
+}
+
+impl PrometheusMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every metric recorded so far, in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let series = self.series.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP stellar_sdk_requests_total Calls made to Horizon.\n");
+        out.push_str("# TYPE stellar_sdk_requests_total counter\n");
+        for ((method, endpoint), s) in series.iter() {
+            let _ = writeln!(
+                out,
+                "stellar_sdk_requests_total{{method=\"{}\",endpoint=\"{}\"}} {}",
+                method, endpoint, s.requests
+            );
+        }
+
+        out.push_str(
+            "# HELP stellar_sdk_request_errors_total Calls to Horizon that failed, by error.\n",
+        );
+        out.push_str("# TYPE stellar_sdk_request_errors_total counter\n");
+        for ((method, endpoint), s) in series.iter() {
+            for (error, count) in &s.errors {
+                let _ = writeln!(
+                    out,
+                    "stellar_sdk_request_errors_total{{method=\"{}\",endpoint=\"{}\",error=\"{}\"}} {}",
+                    method, endpoint, error, count
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP stellar_sdk_request_duration_seconds Latency of the calls made to Horizon.\n",
+        );
+        out.push_str("# TYPE stellar_sdk_request_duration_seconds histogram\n");
+        for ((method, endpoint), s) in series.iter() {
+            let labels = format!("method=\"{}\",endpoint=\"{}\"", method, endpoint);
+
+            let mut cumulative = 0;
+            for (le, count) in LATENCY_BUCKETS.iter().zip(s.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "stellar_sdk_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+                    labels, le, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "stellar_sdk_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                labels, s.requests
+            );
+            let _ = writeln!(
+                out,
+                "stellar_sdk_request_duration_seconds_sum{{{}}} {}",
+                labels, s.latency_sum
+            );
+            let _ = writeln!(
+                out,
+                "stellar_sdk_request_duration_seconds_count{{{}}} {}",
+                labels, s.requests
+            );
+        }
+
+        out
+    }
+}
+
+impl MetricsSink for PrometheusMetrics {
+    fn record(&self, request: &RequestMetrics) {
+        let method = format!("{:?}", request.method);
+        let latency = request.latency.as_secs_f64();
+
+        let mut series = self.series.lock().unwrap();
+        let s = series
+            .entry((method, request.endpoint.clone()))
+            .or_default();
+
+        s.requests += 1;
+        s.latency_sum += latency;
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|le| latency <= *le) {
+            s.buckets[bucket] += 1;
+        }
+        if let Some(error) = &request.error {
+            *s.errors.entry(error.to_string()).or_default() += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::transport::ErrorClass;
+    use crate::types::HttpMethod;
+
+    #[test]
+    fn test_render() {
+        let metrics = PrometheusMetrics::new();
+        let request = |latency_ms, error| RequestMetrics {
+            method: HttpMethod::GET,
+            endpoint: String::from("/accounts/{id}"),
+            latency: Duration::from_millis(latency_ms),
+            error,
+        };
+
+        metrics.record(&request(20, None));
+        metrics.record(&request(
+            300,
+            Some(ErrorClass::Problem(String::from("not_found"))),
+        ));
+        metrics.record(&request(20_000, Some(ErrorClass::Transport)));
+
+        let rendered = metrics.render();
+        for line in [
+            "stellar_sdk_requests_total{method=\"GET\",endpoint=\"/accounts/{id}\"} 3",
+            "stellar_sdk_request_errors_total{method=\"GET\",endpoint=\"/accounts/{id}\",error=\"not_found\"} 1",
+            "stellar_sdk_request_errors_total{method=\"GET\",endpoint=\"/accounts/{id}\",error=\"transport\"} 1",
+            "stellar_sdk_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/accounts/{id}\",le=\"0.01\"} 0",
+            "stellar_sdk_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/accounts/{id}\",le=\"0.025\"} 1",
+            "stellar_sdk_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/accounts/{id}\",le=\"0.5\"} 2",
+            "stellar_sdk_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/accounts/{id}\",le=\"10\"} 2",
+            "stellar_sdk_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/accounts/{id}\",le=\"+Inf\"} 3",
+            "stellar_sdk_request_duration_seconds_sum{method=\"GET\",endpoint=\"/accounts/{id}\"} 20.32",
+            "stellar_sdk_request_duration_seconds_count{method=\"GET\",endpoint=\"/accounts/{id}\"} 3",
+        ] {
+            assert!(rendered.lines().any(|l| l == line), "missing {}", line);
+        }
+    }
+}
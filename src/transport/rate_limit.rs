@@ -0,0 +1,168 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::transport::HttpResponse;
+
+/// The rate limit state Horizon reports in the `X-RateLimit-*` headers of every
+/// response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per window.
+    pub limit: u32,
+    /// Requests left in the current window.
+    pub remaining: u32,
+    /// Time until the window resets, as of the response.
+    pub reset: Duration,
+}
+
+impl RateLimit {
+    /// Reads the rate limit headers, `None` when Horizon did not send them.
+    pub fn from_response(res: &HttpResponse) -> Option<Self> {
+        let header = |name: &str| res.header(name)?.trim().parse::<u64>().ok();
+
+        Some(RateLimit {
+            limit: header("X-RateLimit-Limit")?.try_into().ok()?,
+            remaining: header("X-RateLimit-Remaining")?.try_into().ok()?,
+            reset: Duration::from_secs(header("X-RateLimit-Reset")?),
+        })
+    }
+}
+
+/// A client-side token bucket that spaces out requests so that a [`Server`](crate::Server)
+/// stays under Horizon's rate limit instead of getting 429s.
+///
+/// The bucket also follows the rate limit headers: it never holds more tokens than
+/// Horizon says are left, and waits for the reset once none are.
+///
+/// ```
+/// use std::sync::Arc;
+/// use stellar_sdk::{transport::RateLimiter, Server, ServerOptions};
+///
+/// let options = ServerOptions {
+///     // The public SDF Horizon allows 3600 requests per hour
+///     rate_limiter: Some(Arc::new(RateLimiter::per_hour(3600))),
+///     ..Default::default()
+/// };
+/// let s = Server::new(String::from("https://horizon.stellar.org"), Some(options)).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    // Tokens added per second
+    refill_rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    blocked_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Allows `requests` per `period`, in bursts of up to `requests`.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        let capacity = f64::from(requests.max(1));
+
+        Self {
+            capacity,
+            refill_rate: capacity / period.as_secs_f64().max(f64::EPSILON),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                updated: Instant::now(),
+                blocked_until: None,
+            }),
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_hour(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(3600))
+    }
+
+    /// Takes a token and returns how long to wait before sending the request.
+    pub(crate) fn reserve(&self) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_rate).min(self.capacity);
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+
+        // A negative balance is paid back by waiting for the refill
+        let refill_wait = if bucket.tokens < 0.0 {
+            Duration::from_secs_f64(-bucket.tokens / self.refill_rate)
+        } else {
+            Duration::ZERO
+        };
+        let reset_wait = bucket
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or(Duration::ZERO);
+
+        refill_wait.max(reset_wait)
+    }
+
+    /// Syncs the bucket with the rate limit reported by Horizon.
+    pub(crate) fn observe(&self, rate_limit: &RateLimit) {
+        let mut bucket = self.bucket.lock().unwrap();
+
+        bucket.tokens = bucket.tokens.min(f64::from(rate_limit.remaining));
+        bucket.blocked_until = match rate_limit.remaining {
+            0 => Some(Instant::now() + rate_limit.reset),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_from_response() {
+        let res = HttpResponse::new(200, "{}")
+            .with_header("X-RateLimit-Limit", "3600")
+            .with_header("X-RateLimit-Remaining", "3599")
+            .with_header("X-RateLimit-Reset", "42");
+
+        assert_eq!(
+            Some(RateLimit {
+                limit: 3600,
+                remaining: 3599,
+                reset: Duration::from_secs(42),
+            }),
+            RateLimit::from_response(&res)
+        );
+        assert_eq!(
+            None,
+            RateLimit::from_response(&HttpResponse::new(200, "{}"))
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_throttles_after_burst() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(10));
+
+        assert_eq!(Duration::ZERO, limiter.reserve());
+        assert_eq!(Duration::ZERO, limiter.reserve());
+        assert!(limiter.reserve() > Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_rate_limiter_waits_for_reset() {
+        let limiter = RateLimiter::per_second(100);
+        limiter.observe(&RateLimit {
+            limit: 3600,
+            remaining: 0,
+            reset: Duration::from_secs(30),
+        });
+
+        assert!(limiter.reserve() > Duration::from_secs(29));
+    }
+}
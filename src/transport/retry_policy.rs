@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+
+use crate::transport::HttpResponse;
+use crate::types::HttpMethod;
+use crate::Error;
+
+// What a Server without a retry policy does: a single attempt, as it always has
+pub(crate) const NO_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 1,
+    initial_backoff: Duration::from_millis(500),
+    max_backoff: Duration::from_secs(10),
+    jitter: true,
+    respect_retry_after: true,
+    retry_submission_timeouts: false,
+};
+
+/// When and how often [`Server`](crate::Server) retries a request that failed for a
+/// reason that may go away on its own.
+///
+/// A [`Server`](crate::Server) only retries when `ServerOptions::retry_policy` is set.
+///
+/// GET requests are retried after transport errors, timeouts, 429 and 5xx responses.
+/// Transaction submissions are only retried with `retry_submission_timeouts`, and only
+/// after a 504, which Horizon sends when the transaction did not make it into a ledger
+/// in time, so submitting the same envelope again cannot apply it twice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first one. 1 disables retries.
+    pub max_attempts: u32,
+    /// The wait before the first retry, doubled after every further attempt.
+    pub initial_backoff: Duration,
+    /// The longest wait between two attempts, whether from the backoff or from
+    /// `Retry-After`.
+    pub max_backoff: Duration,
+    /// Waits a random duration between half and all of the backoff, so that many
+    /// clients do not retry in lockstep.
+    pub jitter: bool,
+    /// Waits as long as the `Retry-After` header of a 429 or 503 response asks, up
+    /// to `max_backoff`.
+    pub respect_retry_after: bool,
+    /// Retries transaction submissions that timed out with a 504.
+    pub retry_submission_timeouts: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Up to 3 attempts of GET requests, starting with a 500ms backoff.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            ..NO_RETRY_POLICY
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries, the one of a server without a policy.
+    pub fn none() -> Self {
+        NO_RETRY_POLICY
+    }
+
+    /// How long to wait before the next attempt, or `None` when `result` is final.
+    /// `attempt` is the number of attempts made so far.
+    pub(crate) fn retry_delay(
+        &self,
+        method: HttpMethod,
+        attempt: u32,
+        result: &Result<HttpResponse, Error>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retryable = match (method, result) {
+            (HttpMethod::GET, Err(Error::Transport(_) | Error::Timeout)) => true,
+            (HttpMethod::GET, Ok(res)) => res.status == 429 || (500..600).contains(&res.status),
+            (HttpMethod::POST, Ok(res)) => self.retry_submission_timeouts && res.status == 504,
+            _ => false,
+        };
+        if !retryable {
+            return None;
+        }
+
+        let retry_after = match result {
+            Ok(res) if self.respect_retry_after => res
+                .header("Retry-After")
+                .and_then(retry_after)
+                .map(|wait| wait.min(self.max_backoff)),
+            _ => None,
+        };
+
+        Some(retry_after.unwrap_or_else(|| self.backoff(attempt)))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt - 1))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            rand::thread_rng().gen_range(backoff / 2..=backoff)
+        } else {
+            backoff
+        }
+    }
+}
+
+// Retry-After is either a number of seconds or an HTTP date
+fn retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    let wait = date.with_timezone(&Utc) - Utc::now();
+
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_retry_only_transient_failures() {
+        let unavailable = Ok(HttpResponse::new(503, ""));
+        let not_found = Ok(HttpResponse::new(404, ""));
+        let timeout = Err(Error::Timeout);
+
+        assert_eq!(
+            Some(Duration::from_millis(500)),
+            policy().retry_delay(HttpMethod::GET, 1, &unavailable)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            policy().retry_delay(HttpMethod::GET, 2, &timeout)
+        );
+        assert_eq!(None, policy().retry_delay(HttpMethod::GET, 3, &unavailable));
+        assert_eq!(None, policy().retry_delay(HttpMethod::GET, 1, &not_found));
+        assert_eq!(
+            None,
+            RetryPolicy::none().retry_delay(HttpMethod::GET, 1, &timeout)
+        );
+    }
+
+    #[test]
+    fn test_retry_submissions_only_on_gateway_timeout() {
+        let gateway_timeout = Ok(HttpResponse::new(504, ""));
+        let unavailable = Ok(HttpResponse::new(503, ""));
+        let policy = || RetryPolicy {
+            retry_submission_timeouts: true,
+            ..policy()
+        };
+
+        assert_eq!(
+            None,
+            RetryPolicy::default().retry_delay(HttpMethod::POST, 1, &gateway_timeout)
+        );
+        assert!(policy()
+            .retry_delay(HttpMethod::POST, 1, &gateway_timeout)
+            .is_some());
+        assert_eq!(
+            None,
+            policy().retry_delay(HttpMethod::POST, 1, &unavailable)
+        );
+        assert_eq!(
+            None,
+            policy().retry_delay(HttpMethod::POST, 1, &Err(Error::Timeout))
+        );
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let rate_limited = Ok(HttpResponse::new(429, "").with_header("Retry-After", "7"));
+
+        assert_eq!(
+            Some(Duration::from_secs(7)),
+            policy().retry_delay(HttpMethod::GET, 1, &rate_limited)
+        );
+        assert_eq!(
+            Some(Duration::ZERO),
+            retry_after("Tue, 15 Nov 1994 08:12:31 GMT")
+        );
+    }
+
+    #[test]
+    fn test_retry_after_is_capped() {
+        let a_day = Ok(HttpResponse::new(503, "").with_header("Retry-After", "86400"));
+        let far_future =
+            Ok(HttpResponse::new(429, "")
+                .with_header("Retry-After", "Fri, 31 Dec 9999 23:59:59 GMT"));
+
+        assert_eq!(
+            Some(Duration::from_secs(10)),
+            policy().retry_delay(HttpMethod::GET, 1, &a_day)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(10)),
+            policy().retry_delay(HttpMethod::GET, 1, &far_future)
+        );
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 50,
+            ..policy()
+        };
+
+        assert_eq!(Duration::from_secs(10), policy.backoff(40));
+    }
+}
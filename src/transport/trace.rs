@@ -0,0 +1,205 @@
+use std::time::Duration;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+#[cfg(feature = "tracing")]
+use tracing::field::Empty;
+
+#[cfg(feature = "tracing")]
+use crate::transport::{split_url, RateLimit};
+use crate::transport::{HttpRequest, HttpResponse};
+use crate::Error;
+
+// Query params whose name contains one of these are logged without their value
+#[cfg(feature = "tracing")]
+const SECRET_PARAMS: [&str; 6] = ["auth", "key", "password", "secret", "signature", "token"];
+
+/// The `horizon.request` span of one call to Horizon, retries included, recorded
+/// when the `tracing` feature is enabled and free otherwise.
+///
+/// The span carries the method, endpoint path and query of the request, then the
+/// status, latency, response size, attempts and rate limit headers of its outcome.
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl RequestSpan {
+    pub(crate) fn new(request: &HttpRequest) -> Self {
+        let (url, query) = redacted_query(request);
+
+        Self {
+            span: tracing::info_span!(
+                "horizon.request",
+                method = ?request.method,
+                path = %path(&url),
+                query = %query,
+                status = Empty,
+                latency_ms = Empty,
+                response_size = Empty,
+                attempts = Empty,
+                cache_hit = Empty,
+                rate_limit.limit = Empty,
+                rate_limit.remaining = Empty,
+                rate_limit.reset_secs = Empty,
+            ),
+            started: Instant::now(),
+        }
+    }
+
+    pub(crate) fn cache_hit(&self) {
+        self.span.record("cache_hit", true);
+        tracing::debug!(parent: &self.span, "served from the response cache");
+    }
+
+    pub(crate) fn retry(
+        &self,
+        attempt: u32,
+        delay: Duration,
+        result: &Result<HttpResponse, Error>,
+    ) {
+        let delay_ms = delay.as_millis() as u64;
+
+        match result {
+            Ok(res) => tracing::warn!(
+                parent: &self.span,
+                attempt,
+                delay_ms,
+                status = res.status,
+                "retrying Horizon request"
+            ),
+            Err(e) => tracing::warn!(
+                parent: &self.span,
+                attempt,
+                delay_ms,
+                error = %e,
+                "retrying Horizon request"
+            ),
+        }
+    }
+
+    pub(crate) fn finish(&self, attempts: u32, result: &Result<HttpResponse, Error>) {
+        let latency_ms = self.started.elapsed().as_millis() as u64;
+        self.span.record("attempts", attempts);
+        self.span.record("latency_ms", latency_ms);
+
+        let res = match result {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::warn!(parent: &self.span, latency_ms, error = %e, "Horizon request failed");
+                return;
+            }
+        };
+
+        self.span.record("status", res.status);
+        self.span.record("response_size", res.body.len());
+        if let Some(rate_limit) = RateLimit::from_response(res) {
+            self.span.record("rate_limit.limit", rate_limit.limit);
+            self.span
+                .record("rate_limit.remaining", rate_limit.remaining);
+            self.span
+                .record("rate_limit.reset_secs", rate_limit.reset.as_secs());
+        }
+        tracing::debug!(
+            parent: &self.span,
+            status = res.status,
+            latency_ms,
+            "Horizon responded"
+        );
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl RequestSpan {
+    pub(crate) fn new(_request: &HttpRequest) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn cache_hit(&self) {}
+
+    pub(crate) fn retry(
+        &self,
+        _attempt: u32,
+        _delay: Duration,
+        _result: &Result<HttpResponse, Error>,
+    ) {
+    }
+
+    pub(crate) fn finish(&self, _attempts: u32, _result: &Result<HttpResponse, Error>) {}
+}
+
+/// Emits the event of a stream reconnecting after Horizon dropped it.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn stream_reconnect(url: &str, delay: Duration, cursor: Option<&str>) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        path = %path(url),
+        delay_ms = delay.as_millis() as u64,
+        cursor,
+        "reconnecting Horizon stream"
+    );
+}
+
+// The path of the endpoint, without the scheme and host of the server
+#[cfg(feature = "tracing")]
+fn path(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(url) => String::from(url.path()),
+        Err(_) => String::from(url),
+    }
+}
+
+// The url without its query, and the query of both with the secrets left out
+#[cfg(feature = "tracing")]
+fn redacted_query(request: &HttpRequest) -> (String, String) {
+    let (url, mut query_params) = split_url(&request.url);
+    query_params.extend(request.query_params.clone());
+
+    let query = query_params
+        .iter()
+        .map(|(name, value)| {
+            let lowercase = name.to_ascii_lowercase();
+            match SECRET_PARAMS
+                .iter()
+                .any(|secret| lowercase.contains(secret))
+            {
+                true => format!("{}=[redacted]", name),
+                false => format!("{}={}", name, value),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("&");
+
+    (url, query)
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::types::HttpMethod;
+
+    #[test]
+    fn test_redacted_query() {
+        let request = HttpRequest::new(
+            HttpMethod::GET,
+            "https://horizon.stellar.org/accounts?cursor=7&api_key=hunter2",
+        )
+        .with_query_params(&HashMap::from([
+            (String::from("limit"), String::from("2")),
+            (String::from("Auth_Token"), String::from("hunter2")),
+        ]));
+
+        let (url, query) = redacted_query(&request);
+
+        assert_eq!("/accounts", path(&url));
+        assert_eq!(
+            "Auth_Token=[redacted]&api_key=[redacted]&cursor=7&limit=2",
+            query
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::{self, BufRead, BufReader};
+use std::sync::Arc;
+
+use rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore};
+use ureq::{Agent, AgentBuilder, Error as UreqError, Proxy};
+
+use crate::endpoints::ServerOptions;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport};
+use crate::types::HttpMethod;
+use crate::Error;
+
+/// The default [`HttpTransport`], backed by a reusable `ureq` agent.
+#[derive(Debug, Clone)]
+pub struct UreqTransport {
+    agent: Agent,
+    // Streams stay open for as long as Horizon sends events, so their agent only
+    // has the connect timeout
+    stream_agent: Agent,
+}
+
+impl UreqTransport {
+    pub fn new() -> Self {
+        Self::with_agent(Agent::new())
+    }
+
+    /// Uses `agent` for every request, streams included.
+    pub fn with_agent(agent: Agent) -> Self {
+        Self {
+            stream_agent: agent.clone(),
+            agent,
+        }
+    }
+
+    /// Builds the agent from the timeouts, proxy and root certificates of `options`.
+    /// Streams only apply the connect timeout.
+    pub fn from_options(options: &ServerOptions) -> Result<Self, Error> {
+        let proxy = match &options.proxy {
+            Some(proxy) => Some(
+                Proxy::new(proxy)
+                    .map_err(|e| Error::InvalidUrl(format!("invalid proxy: {}", e)))?,
+            ),
+            None => None,
+        };
+        let tls_config = match &options.root_certificates {
+            Some(pem) => Some(Arc::new(tls_config(pem)?)),
+            None => None,
+        };
+        let builder = || {
+            let mut builder = AgentBuilder::new();
+            if let Some(timeout) = options.connect_timeout {
+                builder = builder.timeout_connect(timeout);
+            }
+            if let Some(proxy) = &proxy {
+                builder = builder.proxy(proxy.clone());
+            }
+            if let Some(tls_config) = &tls_config {
+                builder = builder.tls_config(tls_config.clone());
+            }
+            builder
+        };
+
+        let mut agent = builder();
+        if let Some(timeout) = options.timeout {
+            agent = agent.timeout(timeout);
+        }
+        if let Some(timeout) = options.read_timeout {
+            agent = agent.timeout_read(timeout);
+        }
+
+        Ok(Self {
+            agent: agent.build(),
+            stream_agent: builder().build(),
+        })
+    }
+
+    fn build(agent: &Agent, request: &HttpRequest) -> ureq::Request {
+        let mut req = match request.method {
+            HttpMethod::GET => agent.get(&request.url),
+            HttpMethod::POST => agent.post(&request.url),
+        };
+
+        for (name, value) in request.headers.iter() {
+            req = req.set(name, value);
+        }
+
+        for (name, value) in request.query_params.iter() {
+            req = req.query(name, value);
+        }
+
+        req
+    }
+}
+
+impl Default for UreqTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpTransport for UreqTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+        let res = match Self::build(&self.agent, request).call() {
+            Ok(res) => res,
+            Err(UreqError::Status(_, res)) => res,
+            Err(e) => return Err(transport_error(e)),
+        };
+
+        into_response(res)
+    }
+
+    fn open_stream(&self, request: &HttpRequest) -> Result<Box<dyn BufRead + Send>, Error> {
+        let res = match Self::build(&self.stream_agent, request).call() {
+            Ok(res) => res,
+            Err(UreqError::Status(_, res)) => {
+                return Err(Error::from_response(&request.url, into_response(res)?))
+            }
+            Err(e) => return Err(transport_error(e)),
+        };
+
+        Ok(Box::new(BufReader::new(res.into_reader())))
+    }
+}
+
+// The bundled webpki roots, plus the PEM encoded certificates
+fn tls_config(pem: &[u8]) -> Result<ClientConfig, Error> {
+    let mut root_store = RootCertStore::empty();
+    root_store.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+
+    let certificates = rustls_pemfile::certs(&mut &pem[..])
+        .map_err(|e| Error::InvalidCertificate(e.to_string()))?;
+    if certificates.is_empty() {
+        return Err(Error::InvalidCertificate(String::from(
+            "no certificate found in PEM",
+        )));
+    }
+    for certificate in certificates {
+        root_store
+            .add(&rustls::Certificate(certificate))
+            .map_err(|e| Error::InvalidCertificate(e.to_string()))?;
+    }
+
+    Ok(ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth())
+}
+
+fn into_response(res: ureq::Response) -> Result<HttpResponse, Error> {
+    let mut response = HttpResponse::new(res.status(), "");
+    for name in res.headers_names() {
+        if let Some(value) = res.header(&name) {
+            response = response.with_header(&name, value);
+        }
+    }
+    response.body = res.into_string()?;
+
+    Ok(response)
+}
+
+fn transport_error(e: UreqError) -> Error {
+    let timed_out = std::error::Error::source(&e)
+        .and_then(|source| source.downcast_ref::<io::Error>())
+        .is_some_and(|io_error| io_error.kind() == io::ErrorKind::TimedOut);
+
+    if timed_out {
+        return Error::Timeout;
+    }
+
+    Error::Transport(Box::new(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    const PRIVATE_CA: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIDHTCCAgWgAwIBAgIUeKd4dyWz1PKPQAbPIykBpipx+CYwDQYJKoZIhvcNAQEL\n\
+BQAwHTEbMBkGA1UEAwwSRXhhbXBsZSBQcml2YXRlIENBMCAXDTI2MTAxODA5NDIx\n\
+MFoYDzIxMjYwOTI0MDk0MjEwWjAdMRswGQYDVQQDDBJFeGFtcGxlIFByaXZhdGUg\n\
+Q0EwggEiMA0GCSqGSIb3DQEBAQUAA4IBDwAwggEKAoIBAQCsmlZxDdhQvrUwx6t9\n\
++KWuQ9i3ebZIONF1crMaBd4O39oETKThq0klFyEQlm5kjEaNiUI0nOfh1yIMEdrW\n\
+RfTJdfMhHr6lnwKRZKxKuGWHfkfriQzCiUU8dXGfGV3HklCJ7wel2HTj1EL0ESgA\n\
+5UuM2HpzC64FYcRG/6MphEaHQqDMRwNrTeL9AOMHK4IGymop7UgDxZp+Wj7iPMqd\n\
+tZB0/8jAjiJ4+RWTsDTx2X2ClgFdS1wIpRSb3d5WkikfoXGjOYHS7MmBBmyLz+Hj\n\
+/RXPB4YLRb2f3MYfwaTVNNtuKqX5P93iu6cB4dMwlCnGgkjcNNhKpMZubHovwa7q\n\
+0SjDAgMBAAGjUzBRMB0GA1UdDgQWBBTZLbI/wUIBAmzkQT/9jzmfII1onjAfBgNV\n\
+HSMEGDAWgBTZLbI/wUIBAmzkQT/9jzmfII1onjAPBgNVHRMBAf8EBTADAQH/MA0G\n\
+CSqGSIb3DQEBCwUAA4IBAQAwau0rBHiicw9vXuC3OGZL6KtpgJPRfuAj+GTV3A6n\n\
+6zSN843s8hIIdvLQF0gdB+rU3TdmPH+SaA4KV/soJ+GRp4fmBYsP1liPLCF+8wxR\n\
+sCSxbHEl0sRcrpBeTK4foThrG3EsKFP6/GJjFedRCapO61MgesCATdlcNLQcOqmS\n\
+ee8Lk5r2rrhm7fJEQsG8p7tIFNk6NdHCE64+icxHrW6x6hj93MGjM/rvrXSOTh8G\n\
+pdJltRJcGZtMqnkOHVe/dIovmGCtfJ2SQ2Wxf+NDkTciIQ2SQRha5w1up2aFzplH\n\
+tAkJVLxz97Q9IFSMIwcDKGmOQVI+z2LT+fBQ+dabcFX2\n\
+-----END CERTIFICATE-----";
+
+    #[test]
+    fn test_from_options() {
+        let options = ServerOptions {
+            proxy: Some(String::from("http://proxy.example.com:3128")),
+            root_certificates: Some(PRIVATE_CA.as_bytes().to_vec()),
+            ..Default::default()
+        };
+        assert!(UreqTransport::from_options(&options).is_ok());
+
+        let options = ServerOptions {
+            root_certificates: Some(b"not a certificate".to_vec()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            UreqTransport::from_options(&options),
+            Err(Error::InvalidCertificate(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_timeout() {
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let options = ServerOptions {
+            read_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+
+        let transport = UreqTransport::from_options(&options).unwrap();
+        let res = transport.send(&HttpRequest::new(HttpMethod::GET, &url));
+
+        assert!(matches!(res, Err(Error::Timeout)));
+    }
+
+    #[test]
+    fn test_stream_outlives_timeout() {
+        // Answers with an event stream whose first event comes after the timeout
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\r\n")
+                .unwrap();
+            thread::sleep(Duration::from_millis(300));
+            stream.write_all(b"data: \"hello\"\n\n").unwrap();
+        });
+        let options = ServerOptions {
+            timeout: Some(Duration::from_millis(100)),
+            read_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+
+        let transport = UreqTransport::from_options(&options).unwrap();
+        let mut stream = transport
+            .open_stream(&HttpRequest::new(HttpMethod::GET, &url))
+            .unwrap();
+        let mut line = String::new();
+        stream.read_line(&mut line).unwrap();
+
+        assert_eq!("data: \"hello\"\n", line);
+        server.join().unwrap();
+    }
+}
@@ -1,7 +1,9 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::endpoints::horizon::{AccountSigner, AccountThresholds, Balance, Flags, ResponseLink};
+use crate::types::timestamp;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AccountLinks {
@@ -25,7 +27,8 @@ pub struct Account {
     pub inflation_destination: Option<String>,
     pub home_domain: Option<String>,
     pub last_modified_ledger: u64,
-    pub last_modified_time: String,
+    #[serde(default, with = "timestamp::option")]
+    pub last_modified_time: Option<DateTime<Utc>>,
     pub thresholds: AccountThresholds,
     pub flags: Flags,
     pub balances: Vec<Balance>,
@@ -36,6 +39,53 @@ pub struct Account {
     pub paging_token: String,
 }
 
+/// A single data entry of an account, from `/accounts/{account_id}/data/{key}`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AccountData {
+    /// The value, base64 encoded.
+    pub value: String,
+    pub sponsor: Option<String>,
+}
+
+/// A minimal `/accounts/{id}` document.
+#[cfg(test)]
+pub(crate) fn account_json(base_url: &str, id: &str, sequence: i64) -> serde_json::Value {
+    let link =
+        |path: &str| serde_json::json!({ "href": format!("{}/accounts/{}{}", base_url, id, path) });
+
+    serde_json::json!({
+        "_links": {
+            "self": link(""),
+            "transactions": link("/transactions"),
+            "operations": link("/operations"),
+            "payments": link("/payments"),
+            "effects": link("/effects"),
+            "offers": link("/offers"),
+            "trades": link("/trades"),
+            "data": link("/data/{key}"),
+        },
+        "id": id,
+        "account_id": id,
+        "sequence": sequence.to_string(),
+        "subentry_count": 0,
+        "last_modified_ledger": 1,
+        "last_modified_time": "2022-05-01T14:18:19Z",
+        "thresholds": { "low_threshold": 0, "med_threshold": 0, "high_threshold": 0 },
+        "flags": {
+            "auth_required": false,
+            "auth_revocable": false,
+            "auth_immutable": false,
+            "auth_clawback_enabled": false,
+        },
+        "balances": [],
+        "signers": [],
+        "data": {},
+        "num_sponsoring": 0,
+        "num_sponsored": 0,
+        "paging_token": id,
+    })
+}
+
 impl Account {
     pub fn increment_sequence_number(&mut self) {
         let mut new_sequence: u64 = self.sequence.parse().unwrap();
@@ -113,7 +163,7 @@ mod tests {
                 ),
                 home_domain: None,
                 last_modified_ledger: 40702343,
-                last_modified_time: String::from("2022-05-01T14:18:19Z"),
+                last_modified_time: Some("2022-05-01T14:18:19Z".parse().unwrap()),
                 thresholds: AccountThresholds {
                     low_threshold: 1,
                     med_threshold: 1,
@@ -127,9 +177,9 @@ mod tests {
                 },
                 balances: vec![
                     Balance {
-                        balance: String::from("370.3906091"),
+                        balance: "370.3906091".parse().unwrap(),
                         limit: Some(
-                            String::from("300000.0000000"),
+                            "300000.0000000".parse().unwrap(),
                         ),
                         asset_type: String::from("credit_alphanum4"),
                         asset_code: Some(
@@ -139,10 +189,10 @@ mod tests {
                             String::from("GBX6YI45VU7WNAAKA3RBFDR3I3UKNFHTJPQ5F6KOOKSGYIAM4TRQN54W"),
                         ),
                         buying_liabilities: Some(
-                            String::from("0.0000000"),
+                            "0.0000000".parse().unwrap(),
                         ),
                         selling_liabilities: Some(
-                            String::from("0.0000000"),
+                            "0.0000000".parse().unwrap(),
                         ),
                         last_modified_ledger: Some(
                             40304840,
@@ -180,4 +230,18 @@ mod tests {
 
         assert_eq!(seq, my_account.sequence);
     }
+
+    #[test]
+    fn test_null_last_modified_time() {
+        let mut json = account_json(
+            "https://horizon.stellar.org",
+            "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+            1,
+        );
+        json["last_modified_time"] = serde_json::Value::Null;
+
+        let account: Account = serde_json::from_value(json).unwrap();
+
+        assert_eq!(None, account.last_modified_time);
+    }
 }
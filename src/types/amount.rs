@@ -0,0 +1,233 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use stellar_base::amount::{Amount as BaseAmount, Stroops};
+
+use crate::Error;
+
+const DECIMALS: u32 = 7;
+const STROOPS_PER_UNIT: i64 = 10_000_000;
+
+/// A Stellar amount stored as a whole number of stroops (0.0000001 units).
+///
+/// Horizon sends amounts as strings with 7 decimals, e.g. `"370.3906091"`, which is
+/// also how an `Amount` is parsed, displayed and serialized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+    pub const MAX: Amount = Amount(i64::MAX);
+
+    pub fn from_stroops(stroops: i64) -> Self {
+        Self(stroops)
+    }
+
+    pub fn to_stroops(&self) -> i64 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(&self, other: Amount) -> Option<Amount> {
+        self.0.checked_add(other.0).map(Amount)
+    }
+
+    pub fn checked_sub(&self, other: Amount) -> Option<Amount> {
+        self.0.checked_sub(other.0).map(Amount)
+    }
+
+    pub fn checked_mul(&self, factor: i64) -> Option<Amount> {
+        self.0.checked_mul(factor).map(Amount)
+    }
+
+    pub fn checked_div(&self, divisor: i64) -> Option<Amount> {
+        self.0.checked_div(divisor).map(Amount)
+    }
+
+    pub fn checked_neg(&self) -> Option<Amount> {
+        self.0.checked_neg().map(Amount)
+    }
+
+    /// Adds up all the amounts, returning `None` on overflow.
+    pub fn checked_sum<I: IntoIterator<Item = Amount>>(amounts: I) -> Option<Amount> {
+        amounts
+            .into_iter()
+            .try_fold(Amount::ZERO, |total, amount| total.checked_add(amount))
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let stroops = self.0.unsigned_abs();
+        let unit = STROOPS_PER_UNIT as u64;
+
+        write!(f, "{}{}.{:07}", sign, stroops / unit, stroops % unit)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(Error::InvalidAmount(String::from(s)));
+        }
+        if fraction.len() > DECIMALS as usize {
+            return Err(Error::InvalidAmount(String::from(s)));
+        }
+
+        let fraction_stroops = format!("{:0<7}", fraction)
+            .parse::<i128>()
+            .map_err(|_| Error::InvalidAmount(String::from(s)))?;
+        let stroops = whole
+            .parse::<i128>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(i128::from(STROOPS_PER_UNIT)))
+            .and_then(|whole| whole.checked_add(fraction_stroops))
+            .map(|stroops| if negative { -stroops } else { stroops })
+            .and_then(|stroops| i64::try_from(stroops).ok())
+            .ok_or_else(|| Error::InvalidAmount(String::from(s)))?;
+
+        Ok(Amount(stroops))
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        Amount::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl From<Stroops> for Amount {
+    fn from(stroops: Stroops) -> Self {
+        Amount(stroops.to_i64())
+    }
+}
+
+impl From<Amount> for Stroops {
+    fn from(amount: Amount) -> Self {
+        Stroops::new(amount.0)
+    }
+}
+
+impl TryFrom<Amount> for BaseAmount {
+    type Error = Error;
+
+    fn try_from(amount: Amount) -> Result<Self, Self::Error> {
+        Ok(BaseAmount::from_stroops(&Stroops::from(amount))?)
+    }
+}
+
+impl TryFrom<BaseAmount> for Amount {
+    type Error = Error;
+
+    fn try_from(amount: BaseAmount) -> Result<Self, Self::Error> {
+        Ok(Amount::from(amount.to_stroops()?))
+    }
+}
+
+/// (De)serializes an [`Amount`] written as a whole number of stroops, e.g. the fees
+/// returned by `/fee_stats`.
+pub(crate) mod stroops {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::Amount;
+
+    pub fn serialize<S: Serializer>(amount: &Amount, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&amount.to_stroops())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        s.parse::<i64>()
+            .map(Amount::from_stroops)
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_from_str() {
+        assert_eq!(
+            3703906091,
+            Amount::from_str("370.3906091").unwrap().to_stroops()
+        );
+        assert_eq!(10_000_000, Amount::from_str("1").unwrap().to_stroops());
+        assert_eq!(-5_000_000, Amount::from_str("-0.5").unwrap().to_stroops());
+        assert_eq!(
+            Amount::MAX,
+            Amount::from_str("922337203685.4775807").unwrap()
+        );
+
+        assert!(Amount::from_str("922337203685.4775808").is_err());
+        assert!(Amount::from_str("1.00000001").is_err());
+        assert!(Amount::from_str("1.2.3").is_err());
+        assert!(Amount::from_str(".5").is_err());
+        assert!(Amount::from_str("abc").is_err());
+    }
+
+    #[test]
+    fn test_amount_display() {
+        assert_eq!("370.3906091", Amount::from_stroops(3703906091).to_string());
+        assert_eq!("0.0000001", Amount::from_stroops(1).to_string());
+        assert_eq!("-1.5000000", Amount::from_stroops(-15_000_000).to_string());
+    }
+
+    #[test]
+    fn test_amount_checked_arithmetic() {
+        let a = Amount::from_str("0.1").unwrap();
+        let b = Amount::from_str("0.2").unwrap();
+
+        assert_eq!(Amount::from_str("0.3").unwrap(), a.checked_add(b).unwrap());
+        assert_eq!(Amount::from_str("-0.1").unwrap(), a.checked_sub(b).unwrap());
+        assert_eq!(None, Amount::MAX.checked_add(Amount::from_stroops(1)));
+        assert_eq!(
+            Some(Amount::from_str("0.6").unwrap()),
+            Amount::checked_sum(vec![a, b, a, b])
+        );
+    }
+
+    #[test]
+    fn test_amount_serde() {
+        let amount: Amount = serde_json::from_str("\"12.5\"").unwrap();
+
+        assert_eq!(125_000_000, amount.to_stroops());
+        assert_eq!("\"12.5000000\"", serde_json::to_string(&amount).unwrap());
+    }
+
+    #[test]
+    fn test_amount_to_base_amount() {
+        let amount = Amount::from_str("12.5").unwrap();
+        let base = BaseAmount::try_from(amount).unwrap();
+
+        assert_eq!(amount, Amount::try_from(base).unwrap());
+    }
+}
@@ -1,23 +1,28 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
-use anyhow::bail;
 use regex::Regex;
+use stellar_base::{Asset as BaseAsset, PublicKey};
 
+use crate::Error;
 use crate::StrKey;
 
 #[derive(Debug, Eq)]
 pub struct Asset(String, String, bool); // code, issuer, is_native
 
 impl Asset {
-    pub fn new(code: String, issuer: String) -> Result<Self, anyhow::Error> {
+    pub fn new(code: String, issuer: String) -> Result<Self, Error> {
         if !StrKey::is_valid_ed25519_public_key(&issuer) {
-            bail!("invalid issuer")
+            return Err(Error::InvalidAsset("invalid issuer"));
         }
 
         let re = Regex::new("^[a-zA-Z0-9]{1,12}$").unwrap();
 
         if !re.is_match(&code) {
-            bail!("invalid code")
+            return Err(Error::InvalidAsset("invalid code"));
         }
 
         Ok(Asset(code, issuer, false))
@@ -68,21 +73,24 @@ impl Asset {
     // TODO: from_operation
 }
 
-impl ToString for Asset {
-    fn to_string(&self) -> String {
+impl Display for Asset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if self.2 {
-            return String::from("native");
+            return f.write_str("native");
         }
 
-        format!("{}:{}", self.0, self.1)
+        write!(f, "{}:{}", self.0, self.1)
     }
 }
 impl FromStr for Asset {
-    type Err = anyhow::Error;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let a = String::from(s);
         let parts = a.split(':').collect::<Vec<&str>>();
+        if parts.len() != 2 {
+            return Err(Error::InvalidAsset("expected code:issuer"));
+        }
 
         Self::new(String::from(parts[0]), String::from(parts[1]))
     }
@@ -94,6 +102,21 @@ impl PartialEq for Asset {
     }
 }
 
+impl TryFrom<&Asset> for BaseAsset {
+    type Error = Error;
+
+    fn try_from(asset: &Asset) -> Result<Self, Self::Error> {
+        if asset.2 {
+            return Ok(BaseAsset::new_native());
+        }
+
+        let issuer = PublicKey::from_account_id(&asset.1)
+            .map_err(|_| Error::InvalidAsset("invalid issuer"))?;
+
+        Ok(BaseAsset::new_credit(asset.0.clone(), issuer)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +195,21 @@ mod tests {
         assert_eq!(String::from(asset_str), asset.to_string());
     }
 
+    #[test]
+    fn test_asset_to_base_asset() {
+        let usdc = Asset::new(
+            String::from("USDC"),
+            String::from("GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"),
+        )
+        .unwrap();
+
+        let base_usdc = BaseAsset::try_from(&usdc).unwrap();
+        let base_xlm = BaseAsset::try_from(&Asset::native()).unwrap();
+
+        assert!(!base_usdc.is_native());
+        assert!(base_xlm.is_native());
+    }
+
     #[test]
     fn test_validate_asset() {
         let my_wrong_asset_code = String::from(":#$%");
@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::endpoints::horizon::{Claimant, ResponseLink};
+use crate::types::timestamp;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ClaimableBalanceLinks {
@@ -23,7 +25,8 @@ pub struct ClaimableBalance {
     pub amount: String,
     pub sponsor: Option<String>,
     pub last_modified_ledger: u64,
-    pub last_modified_time: String,
+    #[serde(default, with = "timestamp::option")]
+    pub last_modified_time: Option<DateTime<Utc>>,
     pub claimants: Vec<Claimant>,
     pub paging_token: String,
     pub flags: ClaimableBalanceFlags,
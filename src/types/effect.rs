@@ -1,10 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::horizon::{Reserve, ResponseLink};
+use crate::endpoints::horizon::{Predicate, Reserve, ResponseLink};
+use crate::types::{timestamp, Amount};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct EffectLinks {
-    #[serde(rename(serialize = "self", deserialize = "self"))]
     pub operation: Option<ResponseLink>,
     pub precedes: Option<ResponseLink>,
     pub succeeds: Option<ResponseLink>,
@@ -12,45 +13,413 @@ pub struct EffectLinks {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Effect {
-    _links: EffectLinks,
-    id: String,
-    paging_token: String,
-    account: String,
-    r#type: String,
-    type_i: u8,
-    created_at: String,
-    starting_balance: Option<String>,
-    asset_type: Option<String>,
-    amount: Option<String>,
-    weight: Option<u32>,
-    public_key: Option<String>,
-    key: Option<String>,
-    low_threshold: Option<u8>,
-    med_threshold: Option<u8>,
-    high_threshold: Option<u8>,
-    home_domain: Option<String>,
-    asset_code: Option<String>,
-    asset_issuer: Option<String>,
-    limit: Option<String>,
-    auth_required_flag: Option<bool>,
-    auth_revokable_flag: Option<bool>,
-    name: Option<bool>,
-    value: Option<bool>,
-    new_seq: Option<String>,
-    liquidity_pool_id: Option<String>,
-    balance_type_i: Option<String>,
-    asset: Option<String>,
-    sponsor: Option<String>,
-    new_sponsor: Option<String>,
-    former_sponsor: Option<String>,
-    data_name: Option<String>,
-    signer: Option<String>,
-    fee_bp: Option<String>,
-    total_trustlines: Option<String>,
-    total_shares: Option<String>,
-    shares_received: Option<String>,
-    shares_redeemed: Option<String>,
-    liquidity_pool: Option<String>,
-    shares_revoked: Option<String>,
-    reserves_revoked: Option<Vec<Reserve>>,
+    pub _links: EffectLinks,
+    pub id: String,
+    pub paging_token: String,
+    pub account: String,
+    pub type_i: u32,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: EffectKind,
+}
+
+/// The effect specific fields, selected by the `type` field Horizon returns.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EffectKind {
+    AccountCreated(AccountCreated),
+    AccountRemoved,
+    AccountCredited(BalanceChanged),
+    AccountDebited(BalanceChanged),
+    AccountThresholdsUpdated(AccountThresholdsUpdated),
+    AccountHomeDomainUpdated(AccountHomeDomainUpdated),
+    AccountFlagsUpdated(AccountFlagsUpdated),
+    AccountInflationDestinationUpdated(AccountInflationDestinationUpdated),
+    SignerCreated(SignerChanged),
+    SignerRemoved(SignerChanged),
+    SignerUpdated(SignerChanged),
+    TrustlineCreated(TrustlineChanged),
+    TrustlineRemoved(TrustlineChanged),
+    TrustlineUpdated(TrustlineChanged),
+    TrustlineAuthorized(TrustlineAuthorization),
+    TrustlineDeauthorized(TrustlineAuthorization),
+    TrustlineAuthorizedToMaintainLiabilities(TrustlineAuthorization),
+    TrustlineFlagsUpdated(TrustlineFlagsUpdated),
+    OfferCreated,
+    OfferRemoved,
+    OfferUpdated,
+    Trade(TradeEffect),
+    DataCreated(DataChanged),
+    DataRemoved(DataChanged),
+    DataUpdated(DataChanged),
+    SequenceBumped(SequenceBumped),
+    ClaimableBalanceCreated(ClaimableBalanceChanged),
+    ClaimableBalanceClaimantCreated(ClaimableBalanceClaimantCreated),
+    ClaimableBalanceClaimed(ClaimableBalanceChanged),
+    ClaimableBalanceClawedBack(ClaimableBalanceClawedBack),
+    AccountSponsorshipCreated(AccountSponsorship),
+    AccountSponsorshipUpdated(AccountSponsorship),
+    AccountSponsorshipRemoved(AccountSponsorship),
+    TrustlineSponsorshipCreated(TrustlineSponsorship),
+    TrustlineSponsorshipUpdated(TrustlineSponsorship),
+    TrustlineSponsorshipRemoved(TrustlineSponsorship),
+    DataSponsorshipCreated(DataSponsorship),
+    DataSponsorshipUpdated(DataSponsorship),
+    DataSponsorshipRemoved(DataSponsorship),
+    ClaimableBalanceSponsorshipCreated(ClaimableBalanceSponsorship),
+    ClaimableBalanceSponsorshipUpdated(ClaimableBalanceSponsorship),
+    ClaimableBalanceSponsorshipRemoved(ClaimableBalanceSponsorship),
+    SignerSponsorshipCreated(SignerSponsorship),
+    SignerSponsorshipUpdated(SignerSponsorship),
+    SignerSponsorshipRemoved(SignerSponsorship),
+    LiquidityPoolDeposited(LiquidityPoolDeposited),
+    LiquidityPoolWithdrew(LiquidityPoolWithdrew),
+    LiquidityPoolTrade(LiquidityPoolTrade),
+    LiquidityPoolCreated(LiquidityPoolCreated),
+    LiquidityPoolRemoved(LiquidityPoolRemoved),
+    LiquidityPoolRevoked(LiquidityPoolRevoked),
+    ContractCredited(ContractBalanceChanged),
+    ContractDebited(ContractBalanceChanged),
+    /// An effect type this version of the SDK does not know about yet
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccountCreated {
+    pub starting_balance: Amount,
+}
+
+/// Shared by `account_credited` and `account_debited`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BalanceChanged {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub amount: Amount,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccountThresholdsUpdated {
+    pub low_threshold: u32,
+    pub med_threshold: u32,
+    pub high_threshold: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccountHomeDomainUpdated {
+    pub home_domain: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccountFlagsUpdated {
+    pub auth_required_flag: Option<bool>,
+    pub auth_revokable_flag: Option<bool>,
+    pub auth_immutable_flag: Option<bool>,
+    pub auth_clawback_enabled_flag: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccountInflationDestinationUpdated {
+    pub inflation_destination: String,
+}
+
+/// Shared by `signer_created`, `signer_removed` and `signer_updated`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SignerChanged {
+    pub weight: u32,
+    pub public_key: String,
+    pub key: String,
+}
+
+/// Shared by `trustline_created`, `trustline_removed` and `trustline_updated`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TrustlineChanged {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub liquidity_pool_id: Option<String>,
+    pub limit: Amount,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TrustlineAuthorization {
+    pub trustor: String,
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TrustlineFlagsUpdated {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub trustor: String,
+    pub authorized_flag: Option<bool>,
+    // Horizon spells the field this way
+    #[serde(rename = "authorized_to_maintain_liabilites_flag")]
+    pub authorized_to_maintain_liabilities_flag: Option<bool>,
+    pub clawback_enabled_flag: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TradeEffect {
+    pub seller: String,
+    pub offer_id: String,
+    pub sold_amount: Amount,
+    pub sold_asset_type: String,
+    pub sold_asset_code: Option<String>,
+    pub sold_asset_issuer: Option<String>,
+    pub bought_amount: Amount,
+    pub bought_asset_type: String,
+    pub bought_asset_code: Option<String>,
+    pub bought_asset_issuer: Option<String>,
+}
+
+/// Shared by `data_created`, `data_removed` and `data_updated`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataChanged {
+    pub name: String,
+    // Missing when the data entry is removed
+    pub value: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SequenceBumped {
+    pub new_seq: String,
+}
+
+/// Shared by `claimable_balance_created` and `claimable_balance_claimed`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ClaimableBalanceChanged {
+    pub asset: String,
+    pub balance_id: String,
+    pub amount: Amount,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ClaimableBalanceClaimantCreated {
+    pub asset: String,
+    pub balance_id: String,
+    pub amount: Amount,
+    pub predicate: Predicate,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ClaimableBalanceClawedBack {
+    pub balance_id: String,
+}
+
+/// `sponsor` is set when the sponsorship is created, `former_sponsor` when it is
+/// updated or removed and `new_sponsor` when it is updated.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AccountSponsorship {
+    pub sponsor: Option<String>,
+    pub former_sponsor: Option<String>,
+    pub new_sponsor: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TrustlineSponsorship {
+    pub asset_type: String,
+    pub asset: Option<String>,
+    pub liquidity_pool_id: Option<String>,
+    pub sponsor: Option<String>,
+    pub former_sponsor: Option<String>,
+    pub new_sponsor: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataSponsorship {
+    pub data_name: String,
+    pub sponsor: Option<String>,
+    pub former_sponsor: Option<String>,
+    pub new_sponsor: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ClaimableBalanceSponsorship {
+    pub balance_id: String,
+    pub sponsor: Option<String>,
+    pub former_sponsor: Option<String>,
+    pub new_sponsor: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SignerSponsorship {
+    pub signer: String,
+    pub sponsor: Option<String>,
+    pub former_sponsor: Option<String>,
+    pub new_sponsor: Option<String>,
+}
+
+/// The state of the liquidity pool after the effect.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EffectLiquidityPool {
+    pub id: String,
+    pub fee_bp: u32,
+    pub r#type: String,
+    pub total_trustlines: String,
+    pub total_shares: Amount,
+    pub reserves: Vec<Reserve>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LiquidityPoolDeposited {
+    pub liquidity_pool: EffectLiquidityPool,
+    pub reserves_deposited: Vec<Reserve>,
+    pub shares_received: Amount,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LiquidityPoolWithdrew {
+    pub liquidity_pool: EffectLiquidityPool,
+    pub reserves_received: Vec<Reserve>,
+    pub shares_redeemed: Amount,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LiquidityPoolTrade {
+    pub liquidity_pool: EffectLiquidityPool,
+    pub sold: Reserve,
+    pub bought: Reserve,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LiquidityPoolCreated {
+    pub liquidity_pool: EffectLiquidityPool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LiquidityPoolRemoved {
+    pub liquidity_pool_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LiquidityPoolRevoked {
+    pub liquidity_pool: EffectLiquidityPool,
+    pub reserves_revoked: Vec<Reserve>,
+    pub shares_revoked: Amount,
+}
+
+/// Shared by `contract_credited` and `contract_debited`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ContractBalanceChanged {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub contract: String,
+    pub amount: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_credited_effect() {
+        let effect: Effect = serde_json::from_str(
+            r#"{
+                "_links": {
+                    "operation": {"href": "https://horizon.stellar.org/operations/184717948121542657"},
+                    "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717948121542657-1"},
+                    "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717948121542657-1"}
+                },
+                "id": "0184717948121542657-0000000001",
+                "paging_token": "184717948121542657-1",
+                "account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "type": "account_credited",
+                "type_i": 2,
+                "created_at": "2022-06-01T09:45:12Z",
+                "asset_type": "credit_alphanum4",
+                "asset_code": "USDC",
+                "asset_issuer": "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+                "amount": "12.5000000"
+            }"#,
+        )
+        .unwrap();
+
+        match effect.kind {
+            EffectKind::AccountCredited(credit) => {
+                assert_eq!(Some(String::from("USDC")), credit.asset_code);
+                assert_eq!("12.5000000", credit.amount.to_string());
+            }
+            kind => panic!("expected account_credited, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_liquidity_pool_deposited_effect() {
+        let effect: Effect = serde_json::from_str(
+            r#"{
+                "_links": {
+                    "operation": {"href": "https://horizon.stellar.org/operations/184717952416509953"},
+                    "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717952416509953-1"},
+                    "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717952416509953-1"}
+                },
+                "id": "0184717952416509953-0000000001",
+                "paging_token": "184717952416509953-1",
+                "account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "type": "liquidity_pool_deposited",
+                "type_i": 90,
+                "created_at": "2022-06-01T09:45:18Z",
+                "liquidity_pool": {
+                    "id": "abcdef",
+                    "fee_bp": 30,
+                    "type": "constant_product",
+                    "total_trustlines": "1",
+                    "total_shares": "10.0000000",
+                    "reserves": [
+                        {"asset": "native", "amount": "10.0000000"},
+                        {"asset": "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN", "amount": "10.0000000"}
+                    ]
+                },
+                "reserves_deposited": [
+                    {"asset": "native", "amount": "10.0000000"},
+                    {"asset": "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN", "amount": "10.0000000"}
+                ],
+                "shares_received": "10.0000000"
+            }"#,
+        )
+        .unwrap();
+
+        match effect.kind {
+            EffectKind::LiquidityPoolDeposited(deposit) => {
+                assert_eq!(30, deposit.liquidity_pool.fee_bp);
+                assert_eq!(2, deposit.reserves_deposited.len());
+            }
+            kind => panic!("expected liquidity_pool_deposited, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_data_effect_values_are_strings() {
+        let effect: Effect = serde_json::from_str(
+            r#"{
+                "_links": {
+                    "operation": {"href": "https://horizon.stellar.org/operations/184717956711477249"},
+                    "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717956711477249-2"},
+                    "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717956711477249-2"}
+                },
+                "id": "0184717956711477249-0000000002",
+                "paging_token": "184717956711477249-2",
+                "account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "type": "data_created",
+                "type_i": 40,
+                "created_at": "2022-06-01T09:45:24Z",
+                "name": "config",
+                "value": "dHJ1ZQ=="
+            }"#,
+        )
+        .unwrap();
+
+        match effect.kind {
+            EffectKind::DataCreated(data) => {
+                assert_eq!("config", data.name);
+                assert_eq!(Some(String::from("dHJ1ZQ==")), data.value);
+            }
+            kind => panic!("expected data_created, got {:?}", kind),
+        }
+    }
 }
@@ -1,27 +1,64 @@
 use serde::{Deserialize, Serialize};
 
+use crate::types::{amount, Amount};
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FeeDistribution {
-    pub max: String,
-    pub min: String,
-    pub mode: String,
-    pub p10: String,
-    pub p20: String,
-    pub p30: String,
-    pub p40: String,
-    pub p50: String,
-    pub p60: String,
-    pub p70: String,
-    pub p80: String,
-    pub p90: String,
-    pub p95: String,
-    pub p99: String,
+    #[serde(with = "amount::stroops")]
+    pub max: Amount,
+    #[serde(with = "amount::stroops")]
+    pub min: Amount,
+    #[serde(with = "amount::stroops")]
+    pub mode: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p10: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p20: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p30: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p40: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p50: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p60: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p70: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p80: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p90: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p95: Amount,
+    #[serde(with = "amount::stroops")]
+    pub p99: Amount,
+}
+
+impl FeeDistribution {
+    /// The fee at one of the percentiles Horizon reports (10, 20, ..., 90, 95 or 99).
+    pub fn percentile(&self, percentile: u8) -> Option<Amount> {
+        match percentile {
+            10 => Some(self.p10),
+            20 => Some(self.p20),
+            30 => Some(self.p30),
+            40 => Some(self.p40),
+            50 => Some(self.p50),
+            60 => Some(self.p60),
+            70 => Some(self.p70),
+            80 => Some(self.p80),
+            90 => Some(self.p90),
+            95 => Some(self.p95),
+            99 => Some(self.p99),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FeeStats {
     pub last_ledger: String,
-    pub last_ledger_base_fee: String,
+    #[serde(with = "amount::stroops")]
+    pub last_ledger_base_fee: Amount,
     pub ledger_capacity_usage: String,
     pub fee_charged: FeeDistribution,
     pub max_fee: FeeDistribution,
@@ -2,6 +2,11 @@ use std::fmt::{Debug, Display};
 use std::{error::Error, fmt::Formatter};
 
 use serde::{Deserialize, Serialize};
+use stellar_base::operation_result::OperationResult;
+use stellar_base::xdr::XDRDeserialize;
+use stellar_base::TransactionResult;
+
+use crate::types::result_code::{operation_results, OperationResultCode, TransactionResultCode};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HorizonError {
@@ -24,11 +29,71 @@ impl Error for HorizonError {}
 pub struct ExtraHorizonError {
     pub envelope_xdr: Option<String>,
     pub result_codes: Option<ErrorResultCode>,
+    // Left out of some problems, e.g. tx_malformed
+    #[serde(default)]
     pub result_xdr: String,
 }
 
+impl ExtraHorizonError {
+    /// Decodes `result_xdr`.
+    pub fn result(&self) -> Result<TransactionResult, crate::Error> {
+        Ok(TransactionResult::from_xdr_base64(&self.result_xdr)?)
+    }
+
+    /// Decodes the result of every operation from `result_xdr`.
+    pub fn operation_results(&self) -> Result<Vec<OperationResult>, crate::Error> {
+        Ok(operation_results(&self.result()?))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ErrorResultCode {
     pub transaction: String,
     pub operations: Option<Vec<String>>,
 }
+
+impl ErrorResultCode {
+    pub fn transaction_code(&self) -> TransactionResultCode {
+        TransactionResultCode::from(self.transaction.as_str())
+    }
+
+    pub fn operation_codes(&self) -> Vec<OperationResultCode> {
+        self.operations
+            .iter()
+            .flatten()
+            .map(|code| OperationResultCode::from(code.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_transaction_failed() {
+        let problem: HorizonError = serde_json::from_str(
+            r#"{
+                "type": "https://stellar.org/horizon-errors/transaction_failed",
+                "title": "Transaction Failed",
+                "status": 400,
+                "extras": {
+                    "envelope_xdr": "AAAA",
+                    "result_codes": {"transaction": "tx_failed", "operations": ["op_underfunded"]},
+                    "result_xdr": "AAAAAAAAAGT/////AAAAAQAAAAAAAAAB/////gAAAAA="
+                }
+            }"#,
+        )
+        .unwrap();
+        let extras = problem.extras.unwrap();
+        let codes = extras.result_codes.as_ref().unwrap();
+
+        assert_eq!(TransactionResultCode::Failed, codes.transaction_code());
+        assert_eq!(
+            vec![OperationResultCode::Underfunded],
+            codes.operation_codes()
+        );
+        assert!(extras.result().unwrap().is_failed());
+        assert_eq!(1, extras.operation_results().unwrap().len());
+    }
+}
@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::endpoints::horizon::ResponseLink;
+use crate::types::timestamp;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LedgerLinks {
@@ -24,7 +26,8 @@ pub struct Ledger {
     pub failed_transaction_count: u32,
     pub operation_count: u32,
     pub tx_set_operation_count: u32,
-    pub closed_at: String,
+    #[serde(with = "timestamp")]
+    pub closed_at: DateTime<Utc>,
     pub total_coins: String,
     pub fee_pool: String,
     pub base_fee_in_stroops: u32,
@@ -33,3 +36,67 @@ pub struct Ledger {
     pub protocol_version: u32,
     pub header_xdr: String,
 }
+
+/// A `/ledgers/{sequence}` document whose `prev_hash` is the hash of the ledger before it.
+#[cfg(test)]
+pub(crate) fn ledger_json(sequence: u32) -> String {
+    let links = ["self", "transactions", "operations", "payments", "effects"]
+        .iter()
+        .map(|name| {
+            format!(
+                "\"{}\": {{\"href\": \"https://horizon.stellar.org/ledgers/{}\"}}",
+                name, sequence
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    format!(
+        r#"{{
+            "_links": {{{links}}},
+            "id": "ledger-{sequence}",
+            "paging_token": "{token}",
+            "hash": "hash-{sequence}",
+            "prev_hash": "hash-{prev}",
+            "sequence": {sequence},
+            "successful_transaction_count": 0,
+            "failed_transaction_count": 0,
+            "operation_count": 0,
+            "tx_set_operation_count": 0,
+            "closed_at": "2015-09-30T16:46:54Z",
+            "total_coins": "100000000000.0000000",
+            "fee_pool": "0.0000000",
+            "base_fee_in_stroops": 100,
+            "base_reserve_in_stroops": 100000000,
+            "max_tx_set_size": 50,
+            "protocol_version": 1,
+            "header_xdr": ""
+        }}"#,
+        links = links,
+        sequence = sequence,
+        prev = sequence - 1,
+        token = u64::from(sequence) << 32,
+    )
+}
+
+/// A `/ledgers?limit={limit}` page of `sequences`, whose next link resumes at `next_cursor`.
+#[cfg(test)]
+pub(crate) fn ledger_page_json(sequences: &[u32], limit: u8, next_cursor: u64) -> String {
+    format!(
+        r#"{{
+            "_links": {{
+                "self": {{"href": "https://horizon.stellar.org/ledgers?order=asc"}},
+                "next": {{"href": "https://horizon.stellar.org/ledgers?cursor={}&limit={}&order=asc"}},
+                "prev": {{"href": "https://horizon.stellar.org/ledgers?order=desc"}}
+            }},
+            "_embedded": {{"records": [{}]}}
+        }}"#,
+        next_cursor,
+        limit,
+        sequences
+            .iter()
+            .map(|sequence| ledger_json(*sequence))
+            .collect::<Vec<String>>()
+            .join(",")
+    )
+}
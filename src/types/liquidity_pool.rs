@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::endpoints::horizon::{Reserve, ResponseLink};
+use crate::types::timestamp;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LiquidityPoolLinks {
@@ -21,5 +23,6 @@ pub struct LiquidityPool {
     pub total_shares: String,
     pub reserves: Vec<Reserve>,
     pub last_modified_ledger: u64,
-    pub last_modified_time: String,
+    #[serde(default, with = "timestamp::option")]
+    pub last_modified_time: Option<DateTime<Utc>>,
 }
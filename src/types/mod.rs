@@ -1,4 +1,5 @@
 mod account;
+mod amount;
 mod asset;
 mod asset_horizon;
 mod claimable_balance;
@@ -11,29 +12,61 @@ mod liquidity_pool;
 mod offer;
 mod operation;
 mod order_book;
+mod price;
+mod result_code;
 mod strict_path;
 mod strict_path_source;
+mod submit_transaction_async_response;
 mod submit_transaction_response;
+mod timestamp;
 mod trade;
 mod trade_aggregation;
 mod transaction;
 
-pub use account::Account;
+#[cfg(test)]
+pub(crate) use account::account_json;
+pub use account::{Account, AccountData, AccountLinks};
+pub use amount::Amount;
 pub use asset::Asset;
 pub use asset_horizon::AssetHorizon;
 pub use claimable_balance::ClaimableBalance;
-pub use effect::Effect;
-pub use fee_stats::FeeStats;
-pub use horizon_error::HorizonError;
+pub use effect::{
+    AccountCreated, AccountFlagsUpdated, AccountHomeDomainUpdated,
+    AccountInflationDestinationUpdated, AccountSponsorship, AccountThresholdsUpdated,
+    BalanceChanged, ClaimableBalanceChanged, ClaimableBalanceClaimantCreated,
+    ClaimableBalanceClawedBack, ClaimableBalanceSponsorship, ContractBalanceChanged, DataChanged,
+    DataSponsorship, Effect, EffectKind, EffectLinks, EffectLiquidityPool, LiquidityPoolCreated,
+    LiquidityPoolDeposited, LiquidityPoolRemoved, LiquidityPoolRevoked, LiquidityPoolTrade,
+    LiquidityPoolWithdrew, SequenceBumped, SignerChanged, SignerSponsorship, TradeEffect,
+    TrustlineAuthorization, TrustlineChanged, TrustlineFlagsUpdated, TrustlineSponsorship,
+};
+pub use fee_stats::{FeeDistribution, FeeStats};
+pub use horizon_error::{ErrorResultCode, ExtraHorizonError, HorizonError};
 pub use http_method::HttpMethod;
-pub use ledger::Ledger;
+#[cfg(test)]
+pub(crate) use ledger::{ledger_json, ledger_page_json};
+pub use ledger::{Ledger, LedgerLinks};
 pub use liquidity_pool::LiquidityPool;
 pub use offer::Offer;
-pub use operation::Operation;
+pub use operation::{
+    AccountMerge, AllowTrust, BeginSponsoringFutureReserves, BumpSequence, ChangeTrust,
+    ClaimClaimableBalance, Clawback, ClawbackClaimableBalance, CreateAccount,
+    CreateClaimableBalance, CreatePassiveSellOffer, EndSponsoringFutureReserves,
+    ExtendFootprintTtl, HostFunctionParameter, InvokeHostFunction, LiquidityPoolDeposit,
+    LiquidityPoolWithdraw, ManageData, ManageOffer, Operation, OperationKind, OperationLinks,
+    PathPaymentStrictReceive, PathPaymentStrictSend, Payment, RevokeSponsorship, SetOptions,
+    SetTrustLineFlags,
+};
 pub use order_book::OrderBook;
+pub use price::Price;
+pub use result_code::{operation_results, OperationResultCode, TransactionResultCode};
 pub use strict_path::StrictPath;
 pub use strict_path_source::StrictPathSource;
+pub use submit_transaction_async_response::{
+    AsyncTransactionStatus, SubmitTransactionAsyncResponse,
+};
 pub use submit_transaction_response::SubmitTransactionResponse;
+pub use timestamp::Timestamp;
 pub use trade::Trade;
 pub use trade_aggregation::TradeAggregation;
 pub use transaction::Transaction;
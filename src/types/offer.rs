@@ -1,13 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::horizon::{PriceRShortHand, ResponseLink};
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct OfferLinks {
-    #[serde(rename(serialize = "self", deserialize = "self"))]
-    pub itself: ResponseLink,
-    pub offer_maker: ResponseLink,
-}
+use crate::types::{timestamp, Amount, Price};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct OfferAsset {
@@ -23,10 +17,11 @@ pub struct Offer {
     pub seller: String,
     pub selling: OfferAsset,
     pub buying: OfferAsset,
-    pub amount: String,
-    pub price_r: PriceRShortHand<u32>,
+    pub amount: Amount,
+    pub price_r: Price,
     pub price: String,
     pub last_modified_ledger: u64,
-    pub last_modified_time: String,
+    #[serde(default, with = "timestamp::option")]
+    pub last_modified_time: Option<DateTime<Utc>>,
     pub sponsor: Option<String>,
 }
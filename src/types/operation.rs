@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::horizon::{Claimant, Path, PriceRShortHand, Reserve, ResponseLink};
+use crate::endpoints::horizon::{Claimant, Path, Reserve, ResponseLink};
+use crate::types::{timestamp, Amount, Price};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct OperationLinks {
@@ -19,70 +21,394 @@ pub struct Operation {
     pub paging_token: String,
     pub transaction_successful: bool,
     pub source_account: String,
-    pub r#type: String,
     pub type_i: u32,
-    pub created_at: String,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
     pub transaction_hash: String,
-    pub starting_balance: Option<String>,
-    pub funder: Option<String>,
-    pub account: Option<String>,
-    pub asset_type: Option<String>,
+    pub sponsor: Option<String>,
+    #[serde(flatten)]
+    pub kind: OperationKind,
+}
+
+/// The operation specific fields, selected by the `type` field Horizon returns.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OperationKind {
+    CreateAccount(CreateAccount),
+    Payment(Payment),
+    PathPaymentStrictReceive(PathPaymentStrictReceive),
+    ManageSellOffer(ManageOffer),
+    CreatePassiveSellOffer(CreatePassiveSellOffer),
+    SetOptions(SetOptions),
+    ChangeTrust(ChangeTrust),
+    AllowTrust(AllowTrust),
+    AccountMerge(AccountMerge),
+    Inflation,
+    ManageData(ManageData),
+    BumpSequence(BumpSequence),
+    ManageBuyOffer(ManageOffer),
+    PathPaymentStrictSend(PathPaymentStrictSend),
+    CreateClaimableBalance(CreateClaimableBalance),
+    ClaimClaimableBalance(ClaimClaimableBalance),
+    BeginSponsoringFutureReserves(BeginSponsoringFutureReserves),
+    EndSponsoringFutureReserves(EndSponsoringFutureReserves),
+    RevokeSponsorship(RevokeSponsorship),
+    Clawback(Clawback),
+    ClawbackClaimableBalance(ClawbackClaimableBalance),
+    SetTrustLineFlags(SetTrustLineFlags),
+    LiquidityPoolDeposit(LiquidityPoolDeposit),
+    LiquidityPoolWithdraw(LiquidityPoolWithdraw),
+    InvokeHostFunction(InvokeHostFunction),
+    ExtendFootprintTtl(ExtendFootprintTtl),
+    RestoreFootprint,
+    /// An operation type this version of the SDK does not know about yet
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateAccount {
+    pub starting_balance: Amount,
+    pub funder: String,
+    pub account: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Payment {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub amount: Amount,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PathPaymentStrictReceive {
+    pub asset_type: String,
     pub asset_code: Option<String>,
     pub asset_issuer: Option<String>,
-    pub from: Option<String>,
-    pub to: Option<String>,
-    pub amount: Option<String>,
-    pub into: Option<String>,
-    pub source_amount: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub amount: Amount,
+    pub path: Vec<Path>,
+    pub source_amount: Amount,
+    pub source_max: Amount,
+    pub source_asset_type: String,
     pub source_asset_code: Option<String>,
     pub source_asset_issuer: Option<String>,
-    pub source_asset_type: Option<String>,
-    pub path: Option<Vec<Path>>,
-    pub destination_min: Option<String>,
-    pub offer_id: Option<String>,
-    pub buying_asset_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PathPaymentStrictSend {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub amount: Amount,
+    pub path: Vec<Path>,
+    pub source_amount: Amount,
+    pub destination_min: Amount,
+    pub source_asset_type: String,
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
+}
+
+/// Shared by `manage_sell_offer` and `manage_buy_offer`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ManageOffer {
+    pub offer_id: String,
+    pub amount: Amount,
+    pub price: String,
+    pub price_r: Price,
+    pub buying_asset_type: String,
+    pub buying_asset_code: Option<String>,
+    pub buying_asset_issuer: Option<String>,
+    pub selling_asset_type: String,
+    pub selling_asset_code: Option<String>,
+    pub selling_asset_issuer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreatePassiveSellOffer {
+    pub amount: Amount,
+    pub price: String,
+    pub price_r: Price,
+    pub buying_asset_type: String,
     pub buying_asset_code: Option<String>,
     pub buying_asset_issuer: Option<String>,
-    pub price: Option<String>,
-    pub price_r: Option<PriceRShortHand<u32>>,
-    pub selling_asset_type: Option<String>,
+    pub selling_asset_type: String,
     pub selling_asset_code: Option<String>,
     pub selling_asset_issuer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetOptions {
+    pub home_domain: Option<String>,
+    pub inflation_dest: Option<String>,
+    pub master_key_weight: Option<u32>,
     pub signer_key: Option<String>,
     pub signer_weight: Option<u32>,
-    pub master_key_weight: Option<u32>,
     pub low_threshold: Option<u32>,
     pub med_threshold: Option<u32>,
     pub high_threshold: Option<u32>,
-    pub home_domain: Option<String>,
     pub set_flags: Option<Vec<u32>>,
     pub set_flags_s: Option<Vec<String>>,
     pub clear_flags: Option<Vec<u32>>,
     pub clear_flags_s: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeTrust {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
     pub liquidity_pool_id: Option<String>,
+    pub limit: Amount,
     pub trustee: Option<String>,
-    pub trustor: Option<String>,
-    pub limit: Option<String>,
-    pub authorize: Option<bool>,
+    pub trustor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AllowTrust {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub authorize: bool,
     pub authorize_to_maintain_liabilities: Option<bool>,
-    pub name: Option<String>,
+    pub trustee: String,
+    pub trustor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AccountMerge {
+    pub account: String,
+    pub into: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ManageData {
+    pub name: String,
+    // Missing when the data entry is deleted
     pub value: Option<String>,
-    pub bump_to: Option<String>,
-    pub asset: Option<String>,
-    pub claimants: Option<Claimant>,
-    pub balance_id: Option<String>,
-    pub claimant: Option<String>,
-    pub sponsor: Option<String>,
-    pub sponsored_id: Option<String>,
-    pub begin_sponsor: Option<String>,
-    pub reserves_max: Option<Vec<Reserve>>,
-    pub reserves_min: Option<Vec<Reserve>>,
-    pub min_price: Option<String>,
-    pub min_price_r: Option<PriceRShortHand<u32>>,
-    pub max_price: Option<String>,
-    pub max_price_r: Option<PriceRShortHand<u32>>,
-    pub reserves_deposited: Option<Vec<Reserve>>,
-    pub shares_received: Option<String>,
-    pub shares: Option<String>,
-    pub reserves_received: Option<Vec<Reserve>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BumpSequence {
+    pub bump_to: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateClaimableBalance {
+    pub asset: String,
+    pub amount: Amount,
+    pub claimants: Vec<Claimant>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClaimClaimableBalance {
+    pub balance_id: String,
+    pub claimant: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BeginSponsoringFutureReserves {
+    pub sponsored_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EndSponsoringFutureReserves {
+    pub begin_sponsor: String,
+}
+
+/// Only the fields of the revoked ledger entry are present.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevokeSponsorship {
+    pub account_id: Option<String>,
+    pub claimable_balance_id: Option<String>,
+    pub data_account_id: Option<String>,
+    pub data_name: Option<String>,
+    pub offer_id: Option<String>,
+    pub trustline_account_id: Option<String>,
+    pub trustline_asset: Option<String>,
+    pub trustline_liquidity_pool_id: Option<String>,
+    pub signer_account_id: Option<String>,
+    pub signer_key: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Clawback {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub from: String,
+    pub amount: Amount,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClawbackClaimableBalance {
+    pub balance_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetTrustLineFlags {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub trustor: String,
+    pub set_flags: Option<Vec<u32>>,
+    pub set_flags_s: Option<Vec<String>>,
+    pub clear_flags: Option<Vec<u32>>,
+    pub clear_flags_s: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LiquidityPoolDeposit {
+    pub liquidity_pool_id: String,
+    pub reserves_max: Vec<Reserve>,
+    pub min_price: String,
+    pub min_price_r: Price,
+    pub max_price: String,
+    pub max_price_r: Price,
+    pub reserves_deposited: Vec<Reserve>,
+    pub shares_received: Amount,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LiquidityPoolWithdraw {
+    pub liquidity_pool_id: String,
+    pub reserves_min: Vec<Reserve>,
+    pub shares: Amount,
+    pub reserves_received: Vec<Reserve>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HostFunctionParameter {
+    pub value: String,
+    pub r#type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InvokeHostFunction {
+    pub function: String,
+    pub parameters: Option<Vec<HostFunctionParameter>>,
+    pub address: Option<String>,
+    pub salt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExtendFootprintTtl {
+    pub extend_to: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_operation() {
+        let op: Operation = serde_json::from_str(
+            r#"{
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/operations/184717948121542657"},
+                    "transaction": {"href": "https://horizon.stellar.org/transactions/5f0c2a4dcbc6b7d8f9bb2ebfa6b4d0b4a8d1e5b5e1b4c2f6a7d3e9f0c1b2a3d4"},
+                    "effects": {"href": "https://horizon.stellar.org/operations/184717948121542657/effects"},
+                    "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717948121542657"},
+                    "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717948121542657"}
+                },
+                "id": "184717948121542657",
+                "paging_token": "184717948121542657",
+                "transaction_successful": true,
+                "source_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "type": "payment",
+                "type_i": 1,
+                "created_at": "2022-06-01T09:45:12Z",
+                "transaction_hash": "5f0c2a4dcbc6b7d8f9bb2ebfa6b4d0b4a8d1e5b5e1b4c2f6a7d3e9f0c1b2a3d4",
+                "asset_type": "native",
+                "from": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "to": "GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV",
+                "amount": "10.0000000"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!("184717948121542657", op.paging_token);
+        match op.kind {
+            OperationKind::Payment(payment) => {
+                assert_eq!("native", payment.asset_type);
+                assert_eq!("10.0000000", payment.amount.to_string());
+            }
+            kind => panic!("expected a payment, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_create_claimable_balance_operation() {
+        let op: Operation = serde_json::from_str(
+            r#"{
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/operations/184717952416509953"},
+                    "transaction": {"href": "https://horizon.stellar.org/transactions/0b6e4a1f3c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f"},
+                    "effects": {"href": "https://horizon.stellar.org/operations/184717952416509953/effects"},
+                    "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717952416509953"},
+                    "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717952416509953"}
+                },
+                "id": "184717952416509953",
+                "paging_token": "184717952416509953",
+                "transaction_successful": true,
+                "source_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "type": "create_claimable_balance",
+                "type_i": 14,
+                "created_at": "2022-06-01T09:45:18Z",
+                "transaction_hash": "0b6e4a1f3c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f",
+                "asset": "native",
+                "amount": "1.0000000",
+                "claimants": [
+                    {
+                        "destination": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                        "predicate": {"unconditional": true}
+                    },
+                    {
+                        "destination": "GAP2TJNW7NL52MPB36DZ2PB6PSIBEUEJXDG325BJQKUNDQBPKX3E2DLV",
+                        "predicate": {"abs_before": "2022-07-01T00:00:00Z"}
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        match op.kind {
+            OperationKind::CreateClaimableBalance(balance) => {
+                assert_eq!(2, balance.claimants.len())
+            }
+            kind => panic!("expected create_claimable_balance, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_unknown_operation() {
+        let op: Operation = serde_json::from_str(
+            r#"{
+                "_links": {
+                    "self": {"href": "https://horizon.stellar.org/operations/184717956711477249"},
+                    "transaction": {"href": "https://horizon.stellar.org/transactions/9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b"},
+                    "effects": {"href": "https://horizon.stellar.org/operations/184717956711477249/effects"},
+                    "succeeds": {"href": "https://horizon.stellar.org/effects?order=desc&cursor=184717956711477249"},
+                    "precedes": {"href": "https://horizon.stellar.org/effects?order=asc&cursor=184717956711477249"}
+                },
+                "id": "184717956711477249",
+                "paging_token": "184717956711477249",
+                "transaction_successful": true,
+                "source_account": "GAUZUPTHOMSZEV65VNSRMUDAAE4VBMSRYYAX3UOWYU3BQUZ6OK65NOWM",
+                "type": "some_future_operation",
+                "type_i": 99,
+                "created_at": "2022-06-01T09:45:24Z",
+                "transaction_hash": "9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b"
+            }"#,
+        )
+        .unwrap();
+
+        assert!(matches!(op.kind, OperationKind::Unknown));
+    }
 }
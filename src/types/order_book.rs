@@ -1,12 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::horizon::PriceRShortHand;
+use crate::types::{Amount, Price};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct OrderBookPriceData {
     pub price: String,
-    pub amount: String,
-    pub price_r: PriceRShortHand<u32>,
+    pub amount: Amount,
+    pub price_r: Price,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use stellar_base::amount::Price as BasePrice;
+
+use crate::endpoints::horizon::PriceRShortHand;
+use crate::Error;
+
+/// An exact price as the fraction `n / d`.
+///
+/// Prices compare by value, so `1/2` and `2/4` are equal and no floats are involved.
+/// The denominator is always positive.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Price {
+    n: i64,
+    d: i64,
+}
+
+impl Price {
+    /// Fails when `d` is zero. A negative `d` moves its sign to `n`.
+    pub fn new(n: i64, d: i64) -> Result<Self, Error> {
+        let invalid = || Error::InvalidPrice(format!("{}/{}", n, d));
+
+        match d {
+            0 => Err(invalid()),
+            d if d < 0 => Ok(Self {
+                n: n.checked_neg().ok_or_else(invalid)?,
+                d: d.checked_neg().ok_or_else(invalid)?,
+            }),
+            d => Ok(Self { n, d }),
+        }
+    }
+
+    pub fn n(&self) -> i64 {
+        self.n
+    }
+
+    pub fn d(&self) -> i64 {
+        self.d
+    }
+
+    // Cross multiplied numerators, valid since both denominators are positive
+    fn cross(&self, other: &Price) -> (i128, i128) {
+        (
+            i128::from(self.n) * i128::from(other.d),
+            i128::from(other.n) * i128::from(self.d),
+        )
+    }
+}
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = self.cross(other);
+
+        a == b
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = self.cross(other);
+
+        a.cmp(&b)
+    }
+}
+
+impl Display for Price {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.n, self.d)
+    }
+}
+
+impl<T: Into<i64>> TryFrom<PriceRShortHand<T>> for Price {
+    type Error = Error;
+
+    fn try_from(price: PriceRShortHand<T>) -> Result<Self, Self::Error> {
+        Price::new(price.n.into(), price.d.into())
+    }
+}
+
+impl TryFrom<BasePrice> for Price {
+    type Error = Error;
+
+    fn try_from(price: BasePrice) -> Result<Self, Self::Error> {
+        Price::new(price.numerator().into(), price.denominator().into())
+    }
+}
+
+impl TryFrom<Price> for BasePrice {
+    type Error = Error;
+
+    fn try_from(price: Price) -> Result<Self, Self::Error> {
+        match (i32::try_from(price.n), i32::try_from(price.d)) {
+            (Ok(n), Ok(d)) => Ok(BasePrice::new(n, d)),
+            _ => Err(Error::InvalidPrice(price.to_string())),
+        }
+    }
+}
+
+// Offers send n and d as numbers while trades send them as strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PricePart {
+    Number(i64),
+    String(String),
+}
+
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let price = PriceRShortHand::<PricePart>::deserialize(deserializer)?;
+
+        let part = |part: PricePart| match part {
+            PricePart::Number(n) => Ok(n),
+            PricePart::String(s) => s.parse::<i64>().map_err(serde::de::Error::custom),
+        };
+
+        Price::new(part(price.n)?, part(price.d)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_compare() {
+        let price = |n, d| Price::new(n, d).unwrap();
+
+        assert_eq!(price(1, 2), price(2, 4));
+        assert!(price(1, 3) < price(1, 2));
+        assert!(price(i64::MAX, 1) > price(i64::MAX - 1, 1));
+        assert_eq!(price(-1, 2), price(1, -2));
+        assert!(price(1, -2) < price(1, 3));
+    }
+
+    #[test]
+    fn test_price_new() {
+        assert_eq!(2, Price::new(-1, -2).unwrap().d());
+        assert_eq!(-1, Price::new(1, -2).unwrap().n());
+        assert!(matches!(Price::new(0, 0), Err(Error::InvalidPrice(_))));
+        assert!(matches!(Price::new(1, 0), Err(Error::InvalidPrice(_))));
+        assert!(Price::new(1, i64::MIN).is_err());
+    }
+
+    #[test]
+    fn test_price_deserialize() {
+        let offer_price: Price = serde_json::from_str(r#"{"n": 1, "d": 2}"#).unwrap();
+        let trade_price: Price = serde_json::from_str(r#"{"n": "1", "d": "2"}"#).unwrap();
+
+        assert_eq!(offer_price, trade_price);
+        assert_eq!(2, trade_price.d());
+
+        assert!(serde_json::from_str::<Price>(r#"{"n": 1, "d": 0}"#).is_err());
+        let negative: Price = serde_json::from_str(r#"{"n": "1", "d": "-2"}"#).unwrap();
+        assert_eq!((-1, 2), (negative.n(), negative.d()));
+    }
+
+    #[test]
+    fn test_price_to_base_price() {
+        let price = BasePrice::try_from(Price::new(3, 7).unwrap()).unwrap();
+
+        assert_eq!(3, price.numerator());
+        assert_eq!(Price::new(3, 7).unwrap(), Price::try_from(price).unwrap());
+        assert!(BasePrice::try_from(Price::new(i64::MAX, 1).unwrap()).is_err());
+    }
+}
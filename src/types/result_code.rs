@@ -0,0 +1,215 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use stellar_base::operation_result::OperationResult;
+use stellar_base::transaction_result::InnerTransactionResult;
+use stellar_base::TransactionResult;
+
+// Declares a result code enum from the strings Horizon uses, keeping codes it does
+// not know yet in an Unknown variant
+macro_rules! result_codes {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident = $code:literal => $description:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum $name {
+            $($variant,)*
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The code as Horizon writes it.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $code,)*
+                    $name::Unknown(code) => code,
+                }
+            }
+
+            /// A human-readable explanation of the code.
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $description,)*
+                    $name::Unknown(_) => "unknown result code",
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(code: &str) -> Self {
+                match code {
+                    $($code => $name::$variant,)*
+                    _ => $name::Unknown(String::from(code)),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let code = String::deserialize(deserializer)?;
+
+                Ok($name::from(code.as_str()))
+            }
+        }
+    };
+}
+
+result_codes! {
+    /// The result of a whole transaction, from `extras.result_codes.transaction`.
+    pub enum TransactionResultCode {
+        Success = "tx_success" => "All operations succeeded.",
+        Failed = "tx_failed" => "One of the operations failed, see the operation result codes.",
+        TooEarly = "tx_too_early" => "The ledger close time is before the transaction's minimum time.",
+        TooLate = "tx_too_late" => "The ledger close time is after the transaction's maximum time.",
+        MissingOperation = "tx_missing_operation" => "The transaction has no operations.",
+        BadSeq = "tx_bad_seq" => "The sequence number does not match the source account's.",
+        BadAuth = "tx_bad_auth" => "Too few valid signatures, or signed for the wrong network.",
+        InsufficientBalance = "tx_insufficient_balance" => "The fee would bring the source account below its minimum balance.",
+        NoSourceAccount = "tx_no_source_account" => "The source account does not exist.",
+        InsufficientFee = "tx_insufficient_fee" => "The fee is too small for the transaction to be included.",
+        BadAuthExtra = "tx_bad_auth_extra" => "The transaction has unused signatures.",
+        InternalError = "tx_internal_error" => "An unknown error occurred in Stellar Core.",
+        NotSupported = "tx_not_supported" => "The transaction type is not supported.",
+        FeeBumpInnerSuccess = "tx_fee_bump_inner_success" => "The inner transaction of a fee bump succeeded.",
+        FeeBumpInnerFailed = "tx_fee_bump_inner_failed" => "The inner transaction of a fee bump failed.",
+        BadSponsorship = "tx_bad_sponsorship" => "A sponsorship was not ended within the transaction.",
+        BadMinSeqAgeOrGap = "tx_bad_minseq_age_or_gap" => "The minimum sequence age or ledger gap precondition is not met.",
+        Malformed = "tx_malformed" => "The transaction is malformed.",
+        SorobanInvalid = "tx_soroban_invalid" => "The Soroban specific preconditions are not met.",
+    }
+}
+
+result_codes! {
+    /// The result of a single operation, from `extras.result_codes.operations`.
+    pub enum OperationResultCode {
+        Success = "op_success" => "The operation succeeded.",
+        BadAuth = "op_bad_auth" => "Too few valid signatures for the operation's source account.",
+        NoSourceAccount = "op_no_source_account" => "The operation's source account does not exist.",
+        NotSupported = "op_not_supported" => "The operation is not supported.",
+        TooManySubentries = "op_too_many_subentries" => "The account has reached the maximum number of subentries.",
+        ExceededWorkLimit = "op_exceeded_work_limit" => "The operation did too much work.",
+        TooManySponsoring = "op_too_many_sponsoring" => "The account is sponsoring too many entries.",
+        Malformed = "op_malformed" => "The operation's parameters are invalid.",
+        Underfunded = "op_underfunded" => "The source account does not have enough funds.",
+        LowReserve = "op_low_reserve" => "The account would go below its minimum balance.",
+        LineFull = "op_line_full" => "The destination would go over its trustline limit.",
+        NoDestination = "op_no_destination" => "The destination account does not exist.",
+        NoTrust = "op_no_trust" => "The destination has no trustline for the asset.",
+        NotAuthorized = "op_not_authorized" => "The destination is not authorized to hold the asset.",
+        SrcNoTrust = "op_src_no_trust" => "The source account has no trustline for the asset.",
+        SrcNotAuthorized = "op_src_not_authorized" => "The source account is not authorized to send the asset.",
+        NoIssuer = "op_no_issuer" => "The asset issuer does not exist.",
+        AlreadyExists = "op_already_exists" => "The account already exists.",
+        TooFewOffers = "op_too_few_offers" => "There is no path with enough offers for the payment.",
+        OfferCrossSelf = "op_cross_self" => "The payment would cross an offer of the source account.",
+        OverSourceMax = "op_over_source_max" => "The payment would send more than the maximum allowed.",
+        UnderDestMin = "op_under_dest_min" => "The payment would deliver less than the minimum required.",
+        SellNoTrust = "op_sell_no_trust" => "The account has no trustline for the asset it sells.",
+        BuyNoTrust = "op_buy_no_trust" => "The account has no trustline for the asset it buys.",
+        SellNotAuthorized = "op_sell_not_authorized" => "The account is not authorized to sell the asset.",
+        BuyNotAuthorized = "op_buy_not_authorized" => "The account is not authorized to buy the asset.",
+        OfferNotFound = "op_offer_not_found" => "The offer does not exist.",
+        InvalidLimit = "op_invalid_limit" => "The trustline limit is below the current balance.",
+        HasSubEntries = "op_has_sub_entries" => "The account still has trustlines, offers or data entries.",
+        SeqnumTooFar = "op_seq_num_too_far" => "The account's sequence number is too high to merge it.",
+        DestFull = "op_dest_full" => "The destination would go over its maximum balance.",
+        ImmutableSet = "op_immutable_set" => "The account's flags can no longer be changed.",
+        IsSponsor = "op_is_sponsor" => "The account still sponsors entries.",
+        TooManySigners = "op_too_many_signers" => "The account has reached the maximum number of signers.",
+        BadFlags = "op_bad_flags" => "The flags set and cleared are incompatible.",
+        InvalidInflation = "op_invalid_inflation" => "The inflation destination does not exist.",
+        CantChange = "op_cant_change" => "The flag cannot be changed.",
+        UnknownFlag = "op_unknown_flag" => "The flag is unknown.",
+        ThresholdOutOfRange = "op_threshold_out_of_range" => "A threshold is out of range.",
+        BadSigner = "op_bad_signer" => "The signer is the master key of the account.",
+        InvalidHomeDomain = "op_invalid_home_domain" => "The home domain is invalid.",
+        AuthRevocableRequired = "op_auth_revocable_required" => "Clawback requires the issuer to be auth revocable.",
+        TrustNotRequired = "op_not_required" => "The issuer does not require authorization.",
+        CantRevoke = "op_cant_revoke" => "The issuer cannot revoke authorization.",
+        SelfNotAllowed = "op_self_not_allowed" => "An account cannot trust or authorize itself.",
+        NotTime = "op_not_time" => "Inflation cannot run yet.",
+        NameNotFound = "op_data_name_not_found" => "The data entry does not exist.",
+        InvalidName = "op_data_invalid_name" => "The data entry name is invalid.",
+        BadSeq = "op_bad_seq" => "The sequence number to bump to is invalid.",
+        DoesNotExist = "op_does_not_exist" => "The entry does not exist.",
+        CannotClaim = "op_cannot_claim" => "The predicate of the claimable balance is not met.",
+        AlreadySponsored = "op_already_sponsored" => "The account is already being sponsored.",
+        Recursive = "op_recursive" => "The sponsored account is itself sponsoring.",
+        NotSponsored = "op_not_sponsored" => "The account is not being sponsored.",
+        NotSponsor = "op_not_sponsor" => "The account is not the sponsor of the entry.",
+        OnlyTransferable = "op_only_transferable" => "The sponsorship can only be transferred.",
+        NotClawbackEnabled = "op_not_clawback_enabled" => "Clawback is not enabled for the asset.",
+        BadPrice = "op_bad_price" => "The price is outside the allowed range.",
+        PoolFull = "op_pool_full" => "The liquidity pool reserves are full.",
+        InsufficientReserves = "op_insufficient_reserves" => "The liquidity pool does not have enough reserves.",
+    }
+}
+
+/// The per-operation results in a decoded `result_xdr`, empty when the transaction
+/// failed before any operation ran.
+pub fn operation_results(result: &TransactionResult) -> Vec<OperationResult> {
+    let inner = match result {
+        TransactionResult::Success(success) => return success.results.clone(),
+        TransactionResult::Failed(failed) => return failed.results.clone(),
+        TransactionResult::FeeBumpSuccess(fee_bump) => &fee_bump.result,
+        TransactionResult::FeeBumpFailed(fee_bump) => &fee_bump.result,
+        _ => return Vec::new(),
+    };
+
+    match inner {
+        InnerTransactionResult::Success(success) => success.results.clone(),
+        InnerTransactionResult::Failed(failed) => failed.results.clone(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_result_codes() {
+        assert_eq!(
+            TransactionResultCode::Failed,
+            TransactionResultCode::from("tx_failed")
+        );
+        assert_eq!(
+            OperationResultCode::Underfunded,
+            OperationResultCode::from("op_underfunded")
+        );
+        assert_eq!(
+            OperationResultCode::Unknown(String::from("op_brand_new")),
+            OperationResultCode::from("op_brand_new")
+        );
+        assert_eq!("tx_bad_seq", TransactionResultCode::BadSeq.to_string());
+    }
+
+    #[test]
+    fn test_deserialize_result_codes() {
+        let codes: Vec<OperationResultCode> =
+            serde_json::from_str(r#"["op_success", "op_no_trust"]"#).unwrap();
+
+        assert_eq!(OperationResultCode::NoTrust, codes[1]);
+        assert_eq!(
+            "The destination has no trustline for the asset.",
+            codes[1].description()
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use stellar_base::xdr::XDRDeserialize;
+use stellar_base::TransactionResult;
+
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AsyncTransactionStatus {
+    /// Accepted by Stellar Core and waiting to be included in a ledger.
+    Pending,
+    /// Stellar Core already has this transaction.
+    Duplicate,
+    /// Stellar Core is busy, the same transaction can be submitted again later.
+    TryAgainLater,
+    /// Rejected by Stellar Core, see the error result.
+    Error,
+}
+
+/// The response of `/transactions_async`, see
+/// <https://developers.stellar.org/docs/data/horizon/api-reference/submit-async-transaction>.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitTransactionAsyncResponse {
+    pub hash: String,
+    pub tx_status: AsyncTransactionStatus,
+    #[serde(rename = "errorResultXdr", alias = "error_result_xdr", default)]
+    pub error_result_xdr: Option<String>,
+}
+
+impl SubmitTransactionAsyncResponse {
+    /// Decodes the error result Stellar Core sent back with an `ERROR` status.
+    pub fn error_result(&self) -> Result<Option<TransactionResult>, Error> {
+        match self.error_result_xdr.as_deref() {
+            Some(xdr) if !xdr.is_empty() => Ok(Some(TransactionResult::from_xdr_base64(xdr)?)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_error_result() {
+        let response: SubmitTransactionAsyncResponse = serde_json::from_str(
+            r#"{
+                "hash": "6cbb7f714bd08cea7c30cab7818a35c510cbbfc0a6aa06172a1e94146ecf0165",
+                "tx_status": "ERROR",
+                "errorResultXdr": "AAAAAAAAAGT////7AAAAAA=="
+            }"#,
+        )
+        .unwrap();
+
+        let result = response.error_result().unwrap().unwrap();
+
+        assert_eq!(AsyncTransactionStatus::Error, response.tx_status);
+        assert!(result.is_bad_sequence());
+    }
+
+    #[test]
+    fn test_pending_without_error_result() {
+        let response: SubmitTransactionAsyncResponse = serde_json::from_str(
+            r#"{"hash": "6cbb7f714bd08cea7c30cab7818a35c510cbbfc0a6aa06172a1e94146ecf0165", "tx_status": "PENDING"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(AsyncTransactionStatus::Pending, response.tx_status);
+        assert!(response.error_result().unwrap().is_none());
+    }
+}
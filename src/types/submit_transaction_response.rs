@@ -1,4 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+
+use crate::types::timestamp::{self, Timestamp};
 // https://developers.stellar.org/api/horizon/resources/submit-a-transaction
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,7 +15,8 @@ pub struct SubmitTransactionResponse {
     pub successful: bool,
     pub hash: String,
     pub ledger: i32,
-    pub created_at: String,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
     pub source_account: String,
     pub account_muxed: Option<String>,
     pub account_muxed_id: Option<String>,
@@ -28,8 +32,10 @@ pub struct SubmitTransactionResponse {
     pub fee_meta_xdr: Option<String>,
     pub memo_type: String,
     pub signatures: Vec<String>,
-    pub valid_after: Option<String>,
-    pub valid_before: Option<String>,
+    #[serde(default, deserialize_with = "timestamp::raw::deserialize")]
+    pub valid_after: Option<Timestamp>,
+    #[serde(default, deserialize_with = "timestamp::raw::deserialize")]
+    pub valid_before: Option<Timestamp>,
     pub preconditions: Option<Preconditions>,
     pub fee_bump_transaction: Option<FeeBumpTransaction>,
     pub inner_transaction: Option<InnerTransaction>,
@@ -0,0 +1,196 @@
+//! Serde helpers for the timestamps in Horizon responses.
+//!
+//! Horizon writes timestamps as RFC 3339 strings, e.g. `"2022-05-01T14:18:19Z"`,
+//! except for trade aggregations which use milliseconds since the epoch.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A timestamp that keeps the string Horizon sent next to the parsed value.
+///
+/// Used for the time bounds of a transaction, which the submitter picks freely: a
+/// `valid_before` of `i64::MAX` seconds, for one, is a valid bound but lies past the years
+/// chrono can represent. Such a value is kept in `raw` with `parsed` set to `None` instead of
+/// failing the whole response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub struct Timestamp {
+    pub parsed: Option<DateTime<Utc>>,
+    pub raw: String,
+}
+
+impl From<String> for Timestamp {
+    fn from(raw: String) -> Self {
+        Timestamp {
+            parsed: parse(&raw),
+            raw,
+        }
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.raw
+    }
+}
+
+pub(crate) fn parse(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+pub(crate) fn serialize<S: Serializer>(
+    datetime: &DateTime<Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&datetime.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+
+    parse(&s).ok_or_else(|| de::Error::custom(format!("invalid timestamp: {}", s)))
+}
+
+/// For timestamps Horizon may leave out or send as `null`. A missing, `null` or empty value
+/// becomes `None`, an unparsable one fails like any other timestamp.
+pub(crate) mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        datetime: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match datetime {
+            Some(datetime) => super::serialize(datetime, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        let s = Option::<String>::deserialize(deserializer)?;
+
+        match s.as_deref() {
+            None | Some("") => Ok(None),
+            Some(s) => super::parse(s)
+                .map(Some)
+                .ok_or_else(|| de::Error::custom(format!("invalid timestamp: {}", s))),
+        }
+    }
+}
+
+/// For optional [`Timestamp`]s. A missing, `null` or empty value becomes `None`, any other
+/// string is kept even when it does not parse.
+pub(crate) mod raw {
+    use serde::{Deserialize, Deserializer};
+
+    use super::Timestamp;
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Timestamp>, D::Error> {
+        let s = Option::<String>::deserialize(deserializer)?;
+
+        Ok(s.filter(|s| !s.is_empty()).map(Timestamp::from))
+    }
+}
+
+/// For the milliseconds since the epoch of trade aggregations, e.g. `"1651414699000"`.
+pub(crate) mod millis {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn parse(s: &str) -> Option<DateTime<Utc>> {
+        s.parse::<i64>()
+            .ok()
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        datetime: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&datetime.timestamp_millis().to_string())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        parse(&s).ok_or_else(|| de::Error::custom(format!("invalid timestamp: {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = Utc.with_ymd_and_hms(2022, 5, 1, 14, 18, 19).unwrap();
+
+        assert_eq!(Some(expected), parse("2022-05-01T14:18:19Z"));
+        assert_eq!(Some(expected), parse("2022-05-01T16:18:19+02:00"));
+        assert_eq!(None, parse("1651414699000"));
+        assert_eq!(None, parse("yesterday"));
+        assert_eq!(Some(expected), millis::parse("1651414699000"));
+        assert_eq!(None, millis::parse("2022-05-01T14:18:19Z"));
+    }
+
+    #[test]
+    fn test_optional_timestamp() {
+        #[derive(Deserialize)]
+        struct TimeBounds {
+            #[serde(default, with = "option")]
+            valid_before: Option<DateTime<Utc>>,
+        }
+
+        let bounds = |json| serde_json::from_str::<TimeBounds>(json).map(|t| t.valid_before);
+
+        assert_eq!(None, bounds("{}").unwrap());
+        assert_eq!(None, bounds(r#"{"valid_before": null}"#).unwrap());
+        assert_eq!(None, bounds(r#"{"valid_before": ""}"#).unwrap());
+        assert!(bounds(r#"{"valid_before": "2022-05-01T14:18:19Z"}"#)
+            .unwrap()
+            .is_some());
+        assert!(bounds(r#"{"valid_before": "yesterday"}"#).is_err());
+    }
+
+    #[test]
+    fn test_raw_timestamp() {
+        #[derive(Deserialize)]
+        struct TimeBounds {
+            #[serde(default, deserialize_with = "raw::deserialize")]
+            valid_before: Option<Timestamp>,
+        }
+
+        let bounds = |json| {
+            serde_json::from_str::<TimeBounds>(json)
+                .unwrap()
+                .valid_before
+        };
+
+        assert_eq!(None, bounds("{}"));
+        assert_eq!(None, bounds(r#"{"valid_before": ""}"#));
+
+        let valid_before = bounds(r#"{"valid_before": "2022-05-01T14:18:19Z"}"#).unwrap();
+        assert_eq!(parse("2022-05-01T14:18:19Z"), valid_before.parsed);
+
+        let valid_before = bounds(r#"{"valid_before": "+292277026596-12-04T15:30:07Z"}"#).unwrap();
+        assert_eq!(None, valid_before.parsed);
+        assert_eq!("+292277026596-12-04T15:30:07Z", valid_before.raw);
+        assert_eq!(
+            "\"+292277026596-12-04T15:30:07Z\"",
+            serde_json::to_string(&valid_before).unwrap()
+        );
+    }
+}
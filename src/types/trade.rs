@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::horizon::{PriceRShortHand, ResponseLink};
+use crate::endpoints::horizon::ResponseLink;
+use crate::types::{timestamp, Amount, Price};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TradeLinks {
@@ -16,20 +18,21 @@ pub struct Trade {
     pub _links: TradeLinks,
     pub id: String,
     pub paging_token: String,
-    pub ledger_close_time: String,
+    #[serde(with = "timestamp")]
+    pub ledger_close_time: DateTime<Utc>,
     pub trade_type: String,
     pub base_offer_id: Option<String>,
     pub base_account: Option<String>,
-    pub base_amount: String,
+    pub base_amount: Amount,
     pub base_asset_type: String,
     pub base_asset_code: Option<String>,
     pub base_asset_issuer: Option<String>,
     pub counter_offer_id: Option<String>,
     pub counter_account: Option<String>,
-    pub counter_amount: String,
+    pub counter_amount: Amount,
     pub counter_asset_type: String,
     pub counter_asset_code: Option<String>,
     pub counter_asset_issuer: Option<String>,
     pub base_is_stellar: Option<bool>,
-    pub price: PriceRShortHand<String>,
+    pub price: Price,
 }
@@ -1,20 +1,22 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::horizon::PriceRShortHand;
+use crate::types::{timestamp, Amount, Price};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TradeAggregation {
-    pub timestamp: String,
+    #[serde(with = "timestamp::millis")]
+    pub timestamp: DateTime<Utc>,
     pub trade_count: String,
-    pub base_volume: String,
-    pub counter_volume: String,
+    pub base_volume: Amount,
+    pub counter_volume: Amount,
     pub avg: String,
     pub high: String,
-    pub high_r: PriceRShortHand<String>,
+    pub high_r: Price,
     pub low: String,
-    pub low_r: PriceRShortHand<String>,
+    pub low_r: Price,
     pub open: String,
-    pub open_r: PriceRShortHand<String>,
+    pub open_r: Price,
     pub close: String,
-    pub close_r: PriceRShortHand<String>,
+    pub close_r: Price,
 }
@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::endpoints::horizon::ResponseLink;
+use crate::types::timestamp::{self, Timestamp};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct TransactionLinks {
@@ -25,7 +27,8 @@ pub struct Transaction {
     pub successful: bool,
     pub hash: String,
     pub ledger: u64,
-    pub created_at: String,
+    #[serde(with = "timestamp")]
+    pub created_at: DateTime<Utc>,
     pub source_account: String,
     pub source_account_sequence: String,
     pub fee_account: String,
@@ -37,6 +40,8 @@ pub struct Transaction {
     pub fee_meta_xdr: String,
     pub memo_type: String,
     pub signatures: Vec<String>,
-    pub valid_after: Option<String>,
-    pub valid_before: Option<String>,
+    #[serde(default, deserialize_with = "timestamp::raw::deserialize")]
+    pub valid_after: Option<Timestamp>,
+    #[serde(default, deserialize_with = "timestamp::raw::deserialize")]
+    pub valid_before: Option<Timestamp>,
 }
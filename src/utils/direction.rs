@@ -1,14 +1,16 @@
+use std::fmt::{Display, Formatter};
+
 #[derive(Debug)]
 pub enum Direction {
     Asc,
     Desc,
 }
 
-impl ToString for Direction {
-    fn to_string(&self) -> String {
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Direction::Asc => String::from("asc"),
-            Direction::Desc => String::from("desc"),
+            Direction::Asc => f.write_str("asc"),
+            Direction::Desc => f.write_str("desc"),
         }
     }
 }
@@ -1,3 +1,17 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+// Everything but the unreserved characters of RFC 3986
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encodes a single path segment, e.g. an account ID or a data key.
+pub(crate) fn path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
 #[derive(Debug)]
 pub enum Endpoint {
     None,
@@ -14,13 +28,19 @@ impl Endpoint {
     pub fn as_str(&self) -> String {
         match self {
             Endpoint::None => String::new(),
-            Endpoint::Ledgers(s) => format!("{}{}", "/ledgers/", s),
-            Endpoint::Accounts(s) => format!("{}{}", "/accounts/", s),
-            Endpoint::LiquidityPools(s) => format!("{}{}", "/liquidity_pools/", s),
-            Endpoint::ClaimableBalances(s) => format!("{}{}", "/claimable_balances/", s),
-            Endpoint::Transactions(s) => format!("{}{}", "/transactions", s),
-            Endpoint::Operations(s) => format!("{}{}", "/operations", s),
-            Endpoint::Other(route, s) => format!("/{}{}", route, s),
+            Endpoint::Ledgers(s) => format!("/ledgers/{}", path_segment(s)),
+            Endpoint::Accounts(s) => format!("/accounts/{}", path_segment(s)),
+            Endpoint::LiquidityPools(s) => format!("/liquidity_pools/{}", path_segment(s)),
+            Endpoint::ClaimableBalances(s) => {
+                format!("/claimable_balances/{}", path_segment(s))
+            }
+            Endpoint::Transactions(s) => format!("/transactions/{}", path_segment(s)),
+            Endpoint::Operations(s) => format!("/operations/{}", path_segment(s)),
+            Endpoint::Other(route, s) => format!(
+                "/{}/{}",
+                route.trim_matches('/'),
+                path_segment(s.trim_start_matches('/'))
+            ),
         }
     }
 }
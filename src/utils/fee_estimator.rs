@@ -0,0 +1,174 @@
+use crate::types::{Amount, FeeDistribution, FeeStats};
+
+// The percentiles reported by Horizon's /fee_stats, in order
+const PERCENTILES: [u8; 11] = [10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99];
+
+const DEFAULT_SURGE_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeePriority {
+    /// The 10th percentile, for transactions that can wait.
+    Low,
+    /// The 50th percentile.
+    Medium,
+    /// The 90th percentile, for transactions that should make the next ledger.
+    High,
+    /// Any percentile, rounded up to the next one Horizon reports.
+    Percentile(u8),
+}
+
+impl FeePriority {
+    fn percentile(&self) -> u8 {
+        let percentile = match self {
+            FeePriority::Low => 10,
+            FeePriority::Medium => 50,
+            FeePriority::High => 90,
+            FeePriority::Percentile(p) => *p,
+        };
+
+        PERCENTILES
+            .iter()
+            .copied()
+            .find(|p| *p >= percentile)
+            .unwrap_or(99)
+    }
+}
+
+/// A recommended fee, per operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeEstimate {
+    pub fee: Amount,
+    /// The last ledgers were close to full, so transactions compete on fees.
+    pub surge_pricing: bool,
+    /// The recommended fee was higher than the configured maximum and got capped.
+    pub capped: bool,
+}
+
+/// Recommends a per-operation fee from the `/fee_stats` of Horizon.
+///
+/// While the network is quiet the fee follows what transactions were actually
+/// charged. Once ledger capacity usage reaches the surge threshold it follows the
+/// maximum fees transactions offered instead, since those are what get included.
+///
+/// ```no_run
+/// use stellar_sdk::{utils::{FeeEstimator, FeePriority}, Server};
+///
+/// let s = Server::new(String::from("https://horizon.stellar.org"), None).unwrap();
+/// let estimator = FeeEstimator::new(FeePriority::High).max_fee("0.001".parse().unwrap());
+///
+/// let estimate = s.estimate_fee(&estimator).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FeeEstimator {
+    priority: FeePriority,
+    max_fee: Option<Amount>,
+    surge_threshold: f64,
+}
+
+impl FeeEstimator {
+    pub fn new(priority: FeePriority) -> Self {
+        Self {
+            priority,
+            max_fee: None,
+            surge_threshold: DEFAULT_SURGE_THRESHOLD,
+        }
+    }
+
+    /// The highest fee per operation ever recommended.
+    pub fn max_fee(mut self, max_fee: Amount) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    /// The ledger capacity usage, between 0 and 1, from which surge pricing is
+    /// assumed. Defaults to 0.8.
+    pub fn surge_threshold(mut self, surge_threshold: f64) -> Self {
+        self.surge_threshold = surge_threshold;
+        self
+    }
+
+    pub fn estimate(&self, fee_stats: &FeeStats) -> FeeEstimate {
+        // An unparsable usage is treated as a quiet network
+        let capacity_usage = fee_stats
+            .ledger_capacity_usage
+            .parse::<f64>()
+            .unwrap_or(0.0);
+        let surge_pricing = capacity_usage >= self.surge_threshold;
+
+        let distribution: &FeeDistribution = if surge_pricing {
+            &fee_stats.max_fee
+        } else {
+            &fee_stats.fee_charged
+        };
+        let fee = distribution
+            .percentile(self.priority.percentile())
+            .unwrap_or(distribution.max)
+            .max(fee_stats.last_ledger_base_fee);
+
+        match self.max_fee {
+            Some(max_fee) if fee > max_fee => FeeEstimate {
+                fee: max_fee,
+                surge_pricing,
+                capped: true,
+            },
+            _ => FeeEstimate {
+                fee,
+                surge_pricing,
+                capped: false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee_stats(capacity_usage: &str) -> FeeStats {
+        let fee_charged = r#"{"max": "500", "min": "100", "mode": "100", "p10": "100",
+            "p20": "100", "p30": "100", "p40": "100", "p50": "100", "p60": "100", "p70": "120",
+            "p80": "150", "p90": "200", "p95": "300", "p99": "500"}"#;
+        let max_fee = r#"{"max": "100000", "min": "100", "mode": "1000", "p10": "100",
+            "p20": "200", "p30": "300", "p40": "400", "p50": "1000", "p60": "2000",
+            "p70": "3000", "p80": "4000", "p90": "5000", "p95": "10000", "p99": "50000"}"#;
+
+        serde_json::from_str(&format!(
+            r#"{{"last_ledger": "1", "last_ledger_base_fee": "100",
+                "ledger_capacity_usage": "{}", "fee_charged": {}, "max_fee": {}}}"#,
+            capacity_usage, fee_charged, max_fee
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_estimate_fee_priorities() {
+        let stats = fee_stats("0.5");
+
+        let low = FeeEstimator::new(FeePriority::Low).estimate(&stats);
+        let high = FeeEstimator::new(FeePriority::High).estimate(&stats);
+        let custom = FeeEstimator::new(FeePriority::Percentile(75)).estimate(&stats);
+
+        assert_eq!(100, low.fee.to_stroops());
+        assert_eq!(200, high.fee.to_stroops());
+        assert_eq!(150, custom.fee.to_stroops());
+        assert!(!high.surge_pricing);
+    }
+
+    #[test]
+    fn test_estimate_fee_surge_pricing() {
+        let estimate = FeeEstimator::new(FeePriority::High).estimate(&fee_stats("0.97"));
+
+        assert!(estimate.surge_pricing);
+        assert_eq!(5000, estimate.fee.to_stroops());
+    }
+
+    #[test]
+    fn test_estimate_fee_capped() {
+        let estimate = FeeEstimator::new(FeePriority::Percentile(99))
+            .max_fee(Amount::from_stroops(1000))
+            .estimate(&fee_stats("0.97"));
+
+        assert!(estimate.capped);
+        assert_eq!(1000, estimate.fee.to_stroops());
+    }
+}
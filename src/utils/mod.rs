@@ -1,9 +1,12 @@
 pub mod direction;
 pub mod endpoint;
+pub mod fee_estimator;
 pub mod request;
 pub mod trade_type;
 
 pub use direction::Direction;
 pub use endpoint::Endpoint;
+pub use fee_estimator::{FeeEstimate, FeeEstimator, FeePriority};
+#[allow(deprecated)]
 pub use request::req;
 pub use trade_type::TradeType;
@@ -1,18 +1,25 @@
 use chrono::prelude::*;
+use std::collections::HashMap;
 use ureq;
 
+use crate::api_call::send_request;
+use crate::types::HttpMethod;
+use crate::Server;
+
+#[deprecated(note = "bypasses the transport, retries and headers of Server")]
+#[allow(clippy::result_large_err)]
 pub fn req(url: &str) -> Result<String, ureq::Error> {
     let body: String = ureq::get(url).call()?.into_string()?;
 
     Ok(body)
 }
 
+#[deprecated(note = "bypasses the transport of Server, use get_server_time instead")]
 pub fn get_current_server_time(hostname: &str) -> Option<i64> {
     let response = ureq::get(hostname).call();
 
     let mut server_timestamp: i64 = 0;
-    if response.is_ok() {
-        let response_unwrapped = response.unwrap();
+    if let Ok(response_unwrapped) = response {
         let date_server_str = response_unwrapped.header("Date").unwrap();
         if let Ok(chrono_date) = DateTime::parse_from_rfc2822(date_server_str) {
             server_timestamp = chrono_date.timestamp();
@@ -25,3 +32,26 @@ pub fn get_current_server_time(hostname: &str) -> Option<i64> {
 
     Some(server_timestamp)
 }
+
+/// The time on Horizon, from the `Date` header of its root resource. The request
+/// goes through the transport, retries, rate limiting and metrics of `server`.
+pub fn get_server_time(server: &Server) -> Option<i64> {
+    let response = send_request(&server.server_url, HttpMethod::GET, &HashMap::new(), server);
+
+    let mut server_timestamp: i64 = 0;
+    if let Ok(response_unwrapped) = response {
+        let date_server_str = match response_unwrapped.header("Date") {
+            Some(date) if response_unwrapped.is_success() => date,
+            _ => return None,
+        };
+        if let Ok(chrono_date) = DateTime::parse_from_rfc2822(date_server_str) {
+            server_timestamp = chrono_date.timestamp();
+        }
+    }
+
+    if server_timestamp == 0 {
+        return None;
+    }
+
+    Some(server_timestamp)
+}